@@ -0,0 +1,11 @@
+#!/bin/bash
+set -e
+
+# Builds the WASM fallback used when no native binary matches the platform.
+# Requires wasm-pack: https://rustwasm.github.io/wasm-pack/
+# For browsers, run wasm-pack with --target web instead.
+
+cd "$(dirname "$0")"
+wasm-pack build ../rust --release --target nodejs --out-dir ../npm/wasm --out-name hash_omikuji -- --features wasm
+rm -f wasm/.gitignore wasm/package.json
+echo "Built WASM package in npm/wasm/"
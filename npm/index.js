@@ -11,6 +11,8 @@ const PLATFORMS = {
   'win32-x64': '@elzup/hash-omikuji-win32-x64',
 };
 
+const WASM_OPTIONS = ['--seed', '-s', '--force-year', '--json', '--short'];
+
 function getBinaryPath() {
   const platform = os.platform();
   const arch = os.arch();
@@ -18,8 +20,7 @@ function getBinaryPath() {
 
   const packageName = PLATFORMS[platformKey];
   if (!packageName) {
-    console.error(`Unsupported platform: ${platform}-${arch}`);
-    process.exit(1);
+    return null;
   }
 
   const binaryName = platform === 'win32' ? 'hash-omikuji.exe' : 'hash-omikuji';
@@ -28,9 +29,16 @@ function getBinaryPath() {
     const packagePath = require.resolve(`${packageName}/package.json`);
     return path.join(path.dirname(packagePath), binaryName);
   } catch (e) {
-    console.error(`Platform package not found: ${packageName}`);
+    return null;
+  }
+}
+
+function loadWasm() {
+  try {
+    return require('./wasm/hash_omikuji.js');
+  } catch (e) {
+    console.error(`No pre-built binary or WASM build for ${os.platform()}-${os.arch()}.`);
     console.error('');
-    console.error('The pre-built binary for your platform is not available.');
     console.error('Please build from source:');
     console.error('  1. Install Rust: https://rustup.rs/');
     console.error('  2. Clone the repo and run: cargo build --release');
@@ -38,10 +46,58 @@ function getBinaryPath() {
   }
 }
 
+// Supports the subset of options the WASM build can honour.
+function parseWasmArgs(args) {
+  const options = { seed: null, forceYear: null, json: false, short: false };
+  for (let i = 0; i < args.length; i++) {
+    const [flag, inlineValue] = args[i].split(/=(.*)/s);
+    if (!WASM_OPTIONS.includes(flag)) {
+      console.error(`${args[i]} needs the native binary (the WASM fallback supports ${WASM_OPTIONS.join(', ')})`);
+      process.exit(1);
+    }
+    const value = () => (inlineValue !== undefined ? inlineValue : args[++i]);
+    if (flag === '--seed' || flag === '-s') options.seed = value();
+    if (flag === '--force-year') options.forceYear = parseInt(value(), 10);
+    if (flag === '--json') options.json = true;
+    if (flag === '--short') options.short = true;
+  }
+  return options;
+}
+
+function runWasm(args) {
+  const wasm = loadWasm();
+  const options = parseWasmArgs(args);
+
+  const now = new Date();
+  const isJanuaryFirst = now.getMonth() === 0 && now.getDate() === 1;
+  if (!isJanuaryFirst && options.forceYear === null) {
+    console.error('This command can only be executed on January 1st.');
+    console.error('Use --force-year <YYYY> to override.');
+    process.exit(1);
+  }
+  const year = options.forceYear !== null ? options.forceYear : now.getFullYear();
+  if (!isJanuaryFirst && !options.json) {
+    console.error(`WARNING: Running outside January 1st with --force-year ${year}.\n`);
+  }
+
+  // Same default seed as the native binary: username@hostname
+  const seed = options.seed !== null ? options.seed : `${process.env.USER || 'anonymous'}@${os.hostname()}`;
+  if (options.json) {
+    console.log(wasm.draw(seed, year));
+  } else {
+    process.stdout.write(wasm.drawText(seed, year, options.short));
+  }
+}
+
 function main() {
   const binaryPath = getBinaryPath();
   const args = process.argv.slice(2);
 
+  if (!binaryPath) {
+    runWasm(args);
+    return;
+  }
+
   const child = spawn(binaryPath, args, {
     stdio: 'inherit',
     env: process.env,
@@ -13,7 +13,11 @@ for platform in darwin-arm64 darwin-x64 linux-x64 win32-x64; do
   cd ../..
 done
 
-# Publish main package
+# Publish main package with the WASM fallback
+echo ""
+echo "Building WASM fallback..."
+./build-wasm.sh
+
 echo ""
 echo "Publishing hash-omikuji..."
 npm publish --access public
@@ -0,0 +1,38 @@
+/*
+ * C ABI of the hash-omikuji library (libhash_omikuji.so / .dylib / .dll).
+ *
+ * Strings are UTF-8 and NUL-terminated. Every string returned by the library
+ * must be released with hash_omikuji_free.
+ */
+#ifndef HASH_OMIKUJI_H
+#define HASH_OMIKUJI_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * Draws the fortune for `seed` and `year` (1-9999) and returns it as compact
+ * JSON, the same as `hash-omikuji --format json-compact`. On invalid arguments
+ * the result is {"error": "..."} instead.
+ *
+ * `options_json` may be NULL, or a JSON object with any of:
+ *   "scheme":         1 or 2 (hash input encoding, default 1)
+ *   "namespace":      "acme"
+ *   "beacon":         {"round": 1234, "randomness": "<64 hex chars>"}
+ *   "normalize":      ["nfc", "trim", "casefold", "email"]
+ *   "key":            "<hex>" (secret key, as with --private)
+ *   "schema_version": 1 or 2 (JSON layout, default 1)
+ */
+char *hash_omikuji_draw(const char *seed, uint32_t year, const char *options_json);
+
+/* Frees a string returned by hash_omikuji_draw. NULL is ignored. */
+void hash_omikuji_free(char *json);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* HASH_OMIKUJI_H */
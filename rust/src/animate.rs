@@ -0,0 +1,91 @@
+use crate::luck::Rank;
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+pub const FRAME_DELAY: Duration = Duration::from_millis(35);
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+const SPIN_FRAMES: usize = 4;
+const SHUFFLE_FRAMES: usize = 10;
+/// Clear the current terminal line and return to its start.
+const CLEAR_LINE: &str = "\r\x1b[2K";
+
+/// What is shown for `line` before it settles, ending with `line` itself.
+/// "Label : value" lines spin in place of the value, and luck score lines
+/// also cycle through the rank names before landing on the real one.
+pub fn frames(line: &str, index: usize) -> Vec<String> {
+    let Some((label, _)) = line.split_once(": ") else {
+        return vec![line.to_string()];
+    };
+
+    let spinner = |i: usize| SPINNER[(index + i) % SPINNER.len()];
+    let mut frames: Vec<String> = (0..SPIN_FRAMES).map(|i| format!("{}: {}", label, spinner(i))).collect();
+
+    let rank = Rank::ALL.iter().find(|rank| line.contains(&format!("({})", rank.as_str())));
+    if let Some(rank) = rank {
+        let real = format!("({})", rank.as_str());
+        frames.extend((0..SHUFFLE_FRAMES).map(|i| {
+            let shown = Rank::ALL[(index + i) % Rank::ALL.len()];
+            line.replace(&real, &format!("({}) {}", shown.as_str(), spinner(i)))
+        }));
+    }
+    frames.push(line.to_string());
+    frames
+}
+
+/// Writes `text` line by line, animating each line with `frames`.
+pub fn play(text: &str, out: &mut impl Write, delay: Duration) -> io::Result<()> {
+    for (index, line) in text.lines().enumerate() {
+        let frames = frames(line, index);
+        let last = frames.len() - 1;
+        for (i, frame) in frames.iter().enumerate() {
+            write!(out, "{}{}", CLEAR_LINE, frame)?;
+            out.flush()?;
+            if i < last {
+                thread::sleep(delay);
+            }
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_lines_print_at_once() {
+        assert_eq!(frames("🎍 Hash-Omikuji 2026 🎍", 0), vec!["🎍 Hash-Omikuji 2026 🎍"]);
+        assert_eq!(frames("", 3), vec![""]);
+    }
+
+    #[test]
+    fn test_field_spins_then_settles() {
+        let frames = frames("Lucky Number      : 95", 0);
+        assert_eq!(frames.len(), SPIN_FRAMES + 1);
+        assert_eq!(frames[0], "Lucky Number      : ⠋");
+        assert_eq!(frames.last().unwrap(), "Lucky Number      : 95");
+    }
+
+    #[test]
+    fn test_rank_shuffles_before_settling() {
+        let line = "Debug Luck        :  96 (Excellent)";
+        let frames = frames(line, 0);
+        assert_eq!(frames.len(), SPIN_FRAMES + SHUFFLE_FRAMES + 1);
+        for rank in Rank::ALL {
+            let shown = format!("({})", rank.as_str());
+            assert!(frames.iter().any(|f| f.contains(&shown)), "Never showed {}", shown);
+        }
+        assert_eq!(frames.last().unwrap(), line);
+    }
+
+    #[test]
+    fn test_play_ends_with_full_text() {
+        let mut out = Vec::new();
+        play("A : 1\nB : 2 (Good)\n", &mut out, Duration::ZERO).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let settled: Vec<&str> = out.lines().map(|line| line.rsplit(CLEAR_LINE).next().unwrap()).collect();
+        assert_eq!(settled, vec!["A : 1", "B : 2 (Good)"]);
+    }
+}
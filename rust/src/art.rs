@@ -0,0 +1,176 @@
+use crate::hash::HashBits;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+
+const FIELD_WIDTH: usize = 17;
+const FIELD_HEIGHT: usize = 9;
+// Same symbols as OpenSSH's "randomart", by increasing visit count
+const AUGMENTATION: &[u8] = b" .o+=*BOX@%&#/^";
+
+const IDENTICON_GRID: usize = 5;
+const IDENTICON_CELL: usize = 40;
+const IDENTICON_PADDING: usize = 20;
+
+/// OpenSSH-style "drunken bishop" drawing of the hash.
+/// The bishop starts in the center; every byte moves it 4 times
+/// (2 bits per move, low bits first), and each square shows how often
+/// it was visited. S and E mark the start and end squares.
+pub fn randomart(hash: &HashBits, title: &str) -> String {
+    let mut field = [[0usize; FIELD_WIDTH]; FIELD_HEIGHT];
+    let start = (FIELD_WIDTH / 2, FIELD_HEIGHT / 2);
+    let (mut x, mut y) = start;
+
+    for &byte in hash.bytes() {
+        let mut input = byte;
+        for _ in 0..4 {
+            x = if input & 0x1 != 0 { (x + 1).min(FIELD_WIDTH - 1) } else { x.saturating_sub(1) };
+            y = if input & 0x2 != 0 { (y + 1).min(FIELD_HEIGHT - 1) } else { y.saturating_sub(1) };
+            field[y][x] = (field[y][x] + 1).min(AUGMENTATION.len() - 1);
+            input >>= 2;
+        }
+    }
+
+    let mut output = format!("+{:-^width$}+\n", format!("[{}]", title), width = FIELD_WIDTH);
+    for (row, cells) in field.iter().enumerate() {
+        output.push('|');
+        for (col, &count) in cells.iter().enumerate() {
+            let ch = if (col, row) == start {
+                'S'
+            } else if (col, row) == (x, y) {
+                'E'
+            } else {
+                AUGMENTATION[count] as char
+            };
+            output.push(ch);
+        }
+        output.push_str("|\n");
+    }
+    output.push_str(&format!("+{:-^width$}+\n", "[SHA256]", width = FIELD_WIDTH));
+    output
+}
+
+/// SHA-256 of the whole fingerprint. The identicon is drawn from this
+/// rather than from hash bits, which the fortune already shows as its
+/// lucky values, so matching it means matching all 256 bits.
+fn identicon_digest(hash: &HashBits) -> [u8; 32] {
+    Sha256::digest(hash.bytes()).into()
+}
+
+/// Horizontally symmetric 5x5 identicon pattern.
+/// Digest bit[0..14] fill the left three columns, which are mirrored to the right.
+pub fn identicon_cells(hash: &HashBits) -> [[bool; IDENTICON_GRID]; IDENTICON_GRID] {
+    let digest = identicon_digest(hash);
+    let bits = u16::from_be_bytes([digest[0], digest[1]]);
+    let half = IDENTICON_GRID.div_ceil(2);
+    let mut cells = [[false; IDENTICON_GRID]; IDENTICON_GRID];
+    for (row, cells_row) in cells.iter_mut().enumerate() {
+        for col in 0..half {
+            let on = (bits >> (15 - (row * half + col))) & 1 == 1;
+            cells_row[col] = on;
+            cells_row[IDENTICON_GRID - 1 - col] = on;
+        }
+    }
+    cells
+}
+
+/// Identicon as SVG, colored from digest bytes 2-4.
+pub fn identicon_svg(hash: &HashBits) -> String {
+    let digest = identicon_digest(hash);
+    let color = format!("#{:02X}{:02X}{:02X}", digest[2], digest[3], digest[4]);
+    let size = IDENTICON_GRID * IDENTICON_CELL + IDENTICON_PADDING * 2;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{s}\" height=\"{s}\" viewBox=\"0 0 {s} {s}\">\n",
+        s = size
+    );
+    svg.push_str(&format!("  <rect width=\"{s}\" height=\"{s}\" fill=\"#F0F0F0\"/>\n", s = size));
+    for (row, cells) in identicon_cells(hash).iter().enumerate() {
+        for (col, &on) in cells.iter().enumerate() {
+            if on {
+                svg.push_str(&format!(
+                    "  <rect x=\"{}\" y=\"{}\" width=\"{c}\" height=\"{c}\" fill=\"{}\"/>\n",
+                    IDENTICON_PADDING + col * IDENTICON_CELL,
+                    IDENTICON_PADDING + row * IDENTICON_CELL,
+                    color,
+                    c = IDENTICON_CELL
+                ));
+            }
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+pub fn write_identicon(hash: &HashBits, path: &Path) -> Result<(), String> {
+    fs::write(path, identicon_svg(hash)).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_randomart_dimensions() {
+        let hash = HashBits::from_seed(2026, "test-user");
+        let art = randomart(&hash, "Omikuji 2026");
+        let lines: Vec<_> = art.lines().collect();
+        assert_eq!(lines.len(), FIELD_HEIGHT + 2);
+        for line in lines {
+            assert_eq!(line.chars().count(), FIELD_WIDTH + 2);
+        }
+    }
+
+    #[test]
+    fn test_randomart_start_and_end_markers() {
+        let hash = HashBits::from_seed(2026, "test-user");
+        let art = randomart(&hash, "Omikuji 2026");
+        let field: String = art.lines().skip(1).take(FIELD_HEIGHT).collect();
+        assert_eq!(field.matches('S').count(), 1);
+        assert!(field.matches('E').count() <= 1);
+    }
+
+    #[test]
+    fn test_randomart_differs_per_seed() {
+        let art1 = randomart(&HashBits::from_seed(2026, "alice"), "");
+        let art2 = randomart(&HashBits::from_seed(2026, "bob"), "");
+        assert_ne!(art1, art2);
+    }
+
+    #[test]
+    fn test_identicon_is_symmetric() {
+        for i in 0..50 {
+            let hash = HashBits::from_seed(2026, &format!("test-{}", i));
+            for row in identicon_cells(&hash) {
+                for col in 0..IDENTICON_GRID {
+                    assert_eq!(row[col], row[IDENTICON_GRID - 1 - col]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_identicon_depends_on_whole_fingerprint() {
+        // Same Lucky Number and Lucky Hex (bytes 0-1), different rest
+        let icons: Vec<_> = (0..20)
+            .map(|i| {
+                let fingerprint = HashBits::from_seed(2026, &format!("test-{}", i)).hex_string();
+                identicon_cells(&HashBits::from_fingerprint(&format!("0000{}", &fingerprint[4..])).unwrap())
+            })
+            .collect();
+        assert!(icons.iter().any(|icon| *icon != icons[0]));
+    }
+
+    #[test]
+    fn test_identicon_svg_deterministic() {
+        let hash1 = HashBits::from_seed(2026, "alice");
+        let hash2 = HashBits::from_seed(2026, "alice");
+        assert_eq!(identicon_svg(&hash1), identicon_svg(&hash2));
+    }
+
+    #[test]
+    fn test_snapshot_randomart() {
+        let hash = HashBits::from_seed(2026, "test-user");
+        insta::assert_snapshot!(randomart(&hash, "Omikuji 2026"));
+    }
+}
@@ -0,0 +1,207 @@
+use crate::format;
+use crate::hash::HashBits;
+use crate::output::OmikujiResult;
+use crate::privacy::{display_fingerprint, SeedDisplay};
+use serde::Serialize;
+
+/// CSV columns that identify the draw rather than derive from the hash.
+const SKIPPED_COLUMNS: [&str; 8] = [
+    "year",
+    "seed",
+    "namespace",
+    "beacon_round",
+    "beacon_randomness",
+    "seed_normalization",
+    "hash_scheme",
+    "fingerprint",
+];
+
+#[derive(Debug, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// Two draws whose seeds differ in a single bit.
+#[derive(Debug, Serialize)]
+pub struct Avalanche {
+    /// The seed as given, or its "device:..." form unless revealed
+    pub seed: String,
+    pub flipped_seed: String,
+    pub bit: usize,
+    /// Byte of the seed holding `bit`, and the mask flipped in it
+    pub byte: usize,
+    pub mask: u8,
+    pub fingerprint: String,
+    pub flipped_fingerprint: String,
+    pub bits_changed: u32,
+    pub changed: Vec<FieldChange>,
+    pub unchanged: Vec<String>,
+}
+
+/// `seed` with bit `bit` flipped, counting from the most significant bit
+/// of the first byte (as `HashBits::get_bits` does).
+pub fn flip_bit(seed: &str, bit: usize) -> Result<String, String> {
+    let mut bytes = seed.as_bytes().to_vec();
+    let byte = bytes
+        .get_mut(bit / 8)
+        .ok_or_else(|| format!("bit must be below {} for this seed", seed.len() * 8))?;
+    *byte ^= 0x80 >> (bit % 8);
+    String::from_utf8(bytes).map_err(|_| format!("Flipping bit {} makes the seed invalid UTF-8; choose another --bit", bit))
+}
+
+/// Draws `seed` and the seed with `bit` flipped (default: the lowest bit of
+/// the last byte, which changes only the last character) and compares them.
+/// Both seeds are reported in `display` form, or as given when it is `None`.
+pub fn compare(
+    year: u32,
+    seed: &str,
+    bit: Option<usize>,
+    draw: &dyn Fn(u32, &str) -> (HashBits, OmikujiResult),
+    display: Option<&SeedDisplay>,
+) -> Result<Avalanche, String> {
+    if seed.is_empty() {
+        return Err("avalanche needs a non-empty seed".to_string());
+    }
+    let bit = bit.unwrap_or(seed.len() * 8 - 1);
+    let flipped_seed = flip_bit(seed, bit)?;
+    let (hash, result) = draw(year, seed);
+    let (flipped_hash, flipped_result) = draw(year, &flipped_seed);
+    let bits_changed = hash
+        .bytes()
+        .iter()
+        .zip(flipped_hash.bytes())
+        .map(|(a, b)| (a ^ b).count_ones())
+        .sum();
+
+    let mut changed = Vec::new();
+    let mut unchanged = Vec::new();
+    let columns = format::csv_header()
+        .into_iter()
+        .zip(format::csv_record(&result).into_iter().zip(format::csv_record(&flipped_result)));
+    for (field, (before, after)) in columns.filter(|(field, _)| !SKIPPED_COLUMNS.contains(&field.as_str())) {
+        if before == after {
+            unchanged.push(field);
+        } else {
+            changed.push(FieldChange { field, before, after });
+        }
+    }
+
+    let shown = |seed: &str| display.map_or_else(|| seed.to_string(), |display| display_fingerprint(seed, display));
+    Ok(Avalanche {
+        seed: shown(seed),
+        flipped_seed: shown(&flipped_seed),
+        bit,
+        byte: bit / 8,
+        mask: 0x80 >> (bit % 8),
+        fingerprint: hash.hex_string(),
+        flipped_fingerprint: flipped_hash.hex_string(),
+        bits_changed,
+        changed,
+        unchanged,
+    })
+}
+
+impl Avalanche {
+    pub fn percent_changed(&self) -> f64 {
+        self.bits_changed as f64 * 100.0 / 256.0
+    }
+
+    pub fn format_text(&self) -> String {
+        let marks: String = self
+            .fingerprint
+            .chars()
+            .zip(self.flipped_fingerprint.chars())
+            .map(|(a, b)| if a == b { ' ' } else { '^' })
+            .collect();
+        let mut out = String::new();
+        out.push_str(&format!("Seed         : {:?}\n", self.seed));
+        out.push_str(&format!("Flipped seed : {:?} (bit {})\n", self.flipped_seed, self.bit));
+        out.push_str(&format!("Flipped bit  : byte {}, mask 0x{:02X}\n\n", self.byte, self.mask));
+        out.push_str(&format!("Fingerprint  : {}\n", self.fingerprint));
+        out.push_str(&format!("Flipped      : {}\n", self.flipped_fingerprint));
+        out.push_str(&format!("               {}\n", marks.trim_end()));
+        out.push_str(&format!(
+            "Bits changed : {} / 256 ({:.1}%)\n\n",
+            self.bits_changed,
+            self.percent_changed()
+        ));
+        let total = self.changed.len() + self.unchanged.len();
+        out.push_str(&format!("Changed fields ({} of {}):\n", self.changed.len(), total));
+        let width = self.changed.iter().map(|c| c.field.chars().count()).max().unwrap_or(0);
+        for change in &self.changed {
+            out.push_str(&format!("  {:<width$} : {} → {}\n", change.field, change.before, change.after));
+        }
+        if !self.unchanged.is_empty() {
+            out.push_str(&format!("Unchanged    : {}\n", self.unchanged.join(", ")));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draw(year: u32, seed: &str) -> (HashBits, OmikujiResult) {
+        let hash = HashBits::from_seed(year, seed);
+        let result = OmikujiResult::from_hash(&hash, year, seed);
+        (hash, result)
+    }
+
+    #[test]
+    fn test_flip_bit() {
+        assert_eq!(flip_bit("alice", 39).unwrap(), "alicd");
+        assert_eq!(flip_bit("alice", 2).unwrap(), "Alice");
+        assert!(flip_bit("alice", 40).is_err());
+        assert!(flip_bit("alice", 0).is_err());
+    }
+
+    #[test]
+    fn test_default_flips_last_bit() {
+        let avalanche = compare(2026, "alice", None, &draw, None).unwrap();
+        assert_eq!(avalanche.flipped_seed, "alicd");
+        assert_eq!(avalanche.bit, 39);
+    }
+
+    #[test]
+    fn test_bit_diff_and_fields() {
+        let avalanche = compare(2026, "alice", Some(2), &draw, None).unwrap();
+        let a = HashBits::from_seed(2026, "alice");
+        let b = HashBits::from_seed(2026, "Alice");
+        let expected: u32 = a.bytes().iter().zip(b.bytes()).map(|(x, y)| (x ^ y).count_ones()).sum();
+        assert_eq!(avalanche.bits_changed, expected);
+        // SHA-256 should change about half the bits
+        assert!((64..=192).contains(&avalanche.bits_changed));
+        let total = avalanche.changed.len() + avalanche.unchanged.len();
+        assert_eq!(total, format::csv_header().len() - SKIPPED_COLUMNS.len());
+        assert!(avalanche.changed.iter().all(|c| c.before != c.after));
+    }
+
+    #[test]
+    fn test_format_text() {
+        let text = compare(2026, "alice", None, &draw, None).unwrap().format_text();
+        assert!(text.contains("Flipped seed : \"alicd\" (bit 39)"));
+        assert!(text.contains("Flipped bit  : byte 4, mask 0x01"));
+        assert!(text.contains("Bits changed : "));
+        assert!(text.contains("Changed fields ("));
+    }
+
+    #[test]
+    fn test_seeds_hidden_behind_display() {
+        let display = SeedDisplay::default();
+        let avalanche = compare(2026, "alice@laptop", None, &draw, Some(&display)).unwrap();
+        assert_eq!(avalanche.seed, display_fingerprint("alice@laptop", &display));
+        assert_eq!(avalanche.flipped_seed, display_fingerprint("alice@laptoq", &display));
+        assert_eq!((avalanche.byte, avalanche.mask), (11, 0x01));
+        let json = serde_json::to_string(&avalanche).unwrap();
+        assert!(!json.contains("alice"));
+        assert!(!avalanche.format_text().contains("alice"));
+    }
+
+    #[test]
+    fn test_empty_seed_is_rejected() {
+        assert!(compare(2026, "", None, &draw, None).is_err());
+    }
+}
@@ -0,0 +1,207 @@
+use crate::format::{self, OutputFormat};
+use crate::output::OmikujiResult;
+use crate::schema::SchemaVersion;
+use std::io::{BufRead, Write};
+use std::num::NonZeroUsize;
+use std::thread;
+
+/// Lines drawn per round; results are written after each round, so output
+/// streams while memory stays bounded.
+const CHUNK_LINES: usize = 4096;
+
+/// One input line: a seed, or a `seed,year` row. A line whose last field
+/// isn't a number is taken whole, so seeds may contain commas.
+pub fn parse_line(line: &str, default_year: u32) -> Result<(&str, u32), String> {
+    let (seed, year) = match line.rsplit_once(',') {
+        Some((seed, year)) if year.trim().parse::<u32>().is_ok() => (seed, year.trim().parse().unwrap()),
+        _ => (line, default_year),
+    };
+    if seed.is_empty() {
+        return Err("empty seed".to_string());
+    }
+    if !(1..=9999).contains(&year) {
+        return Err(format!("year must be 1-9999: {}", year));
+    }
+    Ok((seed, year))
+}
+
+/// Output sink for results, in `format`.
+enum Writer<W: Write> {
+    Ndjson(W, SchemaVersion),
+    Csv(Box<csv::Writer<W>>),
+}
+
+impl<W: Write> Writer<W> {
+    fn new(out: W, format: OutputFormat, version: SchemaVersion) -> Result<Self, String> {
+        match format {
+            OutputFormat::Text | OutputFormat::Ndjson => Ok(Writer::Ndjson(out, version)),
+            OutputFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(out);
+                writer.write_record(format::csv_header()).map_err(|e| e.to_string())?;
+                Ok(Writer::Csv(Box::new(writer)))
+            }
+            other => Err(format!("batch supports --format ndjson or csv, not {:?}", other)),
+        }
+    }
+
+    fn write(&mut self, result: &OmikujiResult) -> Result<(), String> {
+        match self {
+            Writer::Ndjson(out, version) => {
+                let line = format::render(result, OutputFormat::Ndjson, *version)?;
+                out.write_all(&line).map_err(|e| e.to_string())
+            }
+            Writer::Csv(writer) => writer.write_record(format::csv_record(result)).map_err(|e| e.to_string()),
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), String> {
+        match self {
+            Writer::Ndjson(out, _) => out.flush(),
+            Writer::Csv(writer) => writer.flush(),
+        }
+        .map_err(|e| e.to_string())
+    }
+}
+
+/// Draws every line of `lines` in parallel and returns the results in
+/// input order.
+fn draw_chunk(
+    lines: &[(usize, String)],
+    default_year: u32,
+    jobs: usize,
+    draw: &(dyn Fn(u32, &str) -> OmikujiResult + Sync),
+) -> Vec<Result<OmikujiResult, String>> {
+    let draw_lines = |lines: &[(usize, String)]| -> Vec<_> {
+        lines
+            .iter()
+            .map(|(number, line)| {
+                let (seed, year) = parse_line(line, default_year).map_err(|msg| format!("line {}: {}", number, msg))?;
+                Ok(draw(year, seed))
+            })
+            .collect()
+    };
+    let per_job = lines.len().div_ceil(jobs).max(1);
+    thread::scope(|scope| {
+        let handles: Vec<_> = lines.chunks(per_job).map(|part| scope.spawn(move || draw_lines(part))).collect();
+        handles.into_iter().flat_map(|handle| handle.join().expect("batch worker panicked")).collect()
+    })
+}
+
+/// Reads seeds from `input` and writes one result per non-empty line to
+/// `out`, as NDJSON (the default) or CSV. Stops at the first invalid line.
+pub fn run(
+    input: impl BufRead,
+    out: impl Write,
+    draw: &(dyn Fn(u32, &str) -> OmikujiResult + Sync),
+    default_year: u32,
+    format: OutputFormat,
+    version: SchemaVersion,
+    jobs: Option<NonZeroUsize>,
+) -> Result<(), String> {
+    let jobs = jobs
+        .or_else(|| thread::available_parallelism().ok())
+        .map_or(1, NonZeroUsize::get);
+    let mut writer = Writer::new(out, format, version)?;
+    let mut lines = input.lines().enumerate();
+    loop {
+        let mut chunk = Vec::with_capacity(CHUNK_LINES);
+        for (index, line) in lines.by_ref() {
+            let line = line.map_err(|e| format!("Failed to read input: {}", e))?;
+            let line = line.trim_end_matches('\r');
+            if !line.trim().is_empty() {
+                chunk.push((index + 1, line.to_string()));
+            }
+            if chunk.len() == CHUNK_LINES {
+                break;
+            }
+        }
+        if chunk.is_empty() {
+            return writer.flush();
+        }
+        for result in draw_chunk(&chunk, default_year, jobs, draw) {
+            writer.write(&result?)?;
+        }
+        writer.flush()?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::{DeriveOptions, HashBits};
+    use crate::normalize::{normalize, Normalization};
+
+    fn draw(year: u32, seed: &str) -> OmikujiResult {
+        OmikujiResult::from_hash(&HashBits::from_seed(year, seed), year, seed)
+    }
+
+    fn run_batch(input: &str, format: OutputFormat, jobs: usize) -> Result<String, String> {
+        let mut out = Vec::new();
+        run(input.as_bytes(), &mut out, &draw, 2026, format, SchemaVersion::V1, NonZeroUsize::new(jobs))?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(parse_line("alice", 2026), Ok(("alice", 2026)));
+        assert_eq!(parse_line("alice,2030", 2026), Ok(("alice", 2030)));
+        assert_eq!(parse_line("Doe, Jane", 2026), Ok(("Doe, Jane", 2026)));
+        assert!(parse_line("alice,0", 2026).is_err());
+        assert!(parse_line(",2030", 2026).is_err());
+    }
+
+    #[test]
+    fn test_ndjson_in_input_order() {
+        let seeds: Vec<String> = (0..100).map(|i| format!("user-{}", i)).collect();
+        let output = run_batch(&seeds.join("\n"), OutputFormat::Ndjson, 4).unwrap();
+        let fingerprints: Vec<String> = output
+            .lines()
+            .map(|line| OmikujiResult::from_json(line).unwrap().fingerprint)
+            .collect();
+        let expected: Vec<String> = seeds.iter().map(|seed| draw(2026, seed).fingerprint).collect();
+        assert_eq!(fingerprints, expected);
+    }
+
+    #[test]
+    fn test_year_column_and_blank_lines() {
+        let output = run_batch("alice\n\nbob,2030\r\n", OutputFormat::Text, 2).unwrap();
+        let years: Vec<u32> = output.lines().map(|line| OmikujiResult::from_json(line).unwrap().year).collect();
+        assert_eq!(years, vec![2026, 2030]);
+    }
+
+    #[test]
+    fn test_csv_has_one_header() {
+        let output = run_batch("alice\nbob\ncarol", OutputFormat::Csv, 2).unwrap();
+        let expected = format::format_csv(&[&draw(2026, "alice"), &draw(2026, "bob"), &draw(2026, "carol")]).unwrap();
+        assert_eq!(output.as_bytes(), expected);
+    }
+
+    #[test]
+    fn test_csv_records_derivation_inputs() {
+        let namespaced = |year: u32, seed: &str| {
+            let (seed, steps) = normalize(seed, &[Normalization::Trim, Normalization::Casefold]);
+            let options = DeriveOptions { namespace: Some("acme"), ..Default::default() };
+            OmikujiResult::from_hash(&HashBits::derive(year, &seed, &options), year, &seed).with_seed_normalization(&steps)
+        };
+        let mut out = Vec::new();
+        run(" Alice ".as_bytes(), &mut out, &namespaced, 2026, OutputFormat::Csv, SchemaVersion::V1, None).unwrap();
+        let mut reader = csv::Reader::from_reader(out.as_slice());
+        let header = reader.headers().unwrap().clone();
+        let record = reader.records().next().unwrap().unwrap();
+        let column = |name: &str| &record[header.iter().position(|h| h == name).unwrap()];
+        assert_eq!(column("namespace"), "acme");
+        assert_eq!(column("seed_normalization"), "trim,casefold");
+        assert_eq!(column("hash_scheme"), "2");
+        assert_eq!(column("beacon_round"), "");
+    }
+
+    #[test]
+    fn test_invalid_line_reports_line_number() {
+        assert_eq!(run_batch("alice\nbob,99999", OutputFormat::Ndjson, 1).unwrap_err(), "line 2: year must be 1-9999: 99999");
+    }
+
+    #[test]
+    fn test_rejects_other_formats() {
+        assert!(run_batch("alice", OutputFormat::Yaml, 1).is_err());
+    }
+}
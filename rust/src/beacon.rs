@@ -0,0 +1,105 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+const RANDOMNESS_LEN: usize = 32;
+
+/// A published randomness beacon round, e.g. from drand. Mixing it into
+/// the derivation means nobody could compute the fortune before the round
+/// was published.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Beacon {
+    pub round: u64,
+    /// 32 bytes, lowercase hex
+    pub randomness: String,
+}
+
+/// drand's JSON for a round (`curl https://api.drand.sh/public/latest`).
+#[derive(Deserialize)]
+struct BeaconFile {
+    round: u64,
+    randomness: String,
+    #[serde(default)]
+    signature: Option<String>,
+}
+
+impl Beacon {
+    /// Parses a drand-style beacon file. When the file includes the round's
+    /// signature, the randomness must be its SHA-256, as drand defines it.
+    /// (The BLS signature itself is not checked.)
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let file: BeaconFile = serde_json::from_str(json).map_err(|e| format!("Invalid beacon file: {}", e))?;
+        let randomness = hex::decode(file.randomness.trim())
+            .ok()
+            .filter(|bytes| bytes.len() == RANDOMNESS_LEN)
+            .ok_or_else(|| format!("Beacon randomness must be {} hex chars", RANDOMNESS_LEN * 2))?;
+        if let Some(signature) = file.signature {
+            let signature = hex::decode(signature.trim()).map_err(|_| "Beacon signature must be hex".to_string())?;
+            if Sha256::digest(&signature).as_slice() != randomness.as_slice() {
+                return Err(format!("Beacon randomness does not match the signature of round {}", file.round));
+            }
+        }
+        Ok(Self {
+            round: file.round,
+            randomness: hex::encode(randomness),
+        })
+    }
+
+    /// The field mixed into the hash input: "{round}:{randomness}".
+    pub fn input_field(&self) -> String {
+        format!("{}:{}", self.round, self.randomness)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let valid = self.randomness.len() == RANDOMNESS_LEN * 2
+            && self.randomness.chars().all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c));
+        if !valid {
+            return Err(format!("beacon randomness must be {} lowercase hex chars", RANDOMNESS_LEN * 2));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGNATURE: &str = "a1b2c3";
+
+    fn beacon_json(randomness: &str, signature: Option<&str>) -> String {
+        match signature {
+            Some(sig) => format!(r#"{{"round": 42, "randomness": "{}", "signature": "{}"}}"#, randomness, sig),
+            None => format!(r#"{{"round": 42, "randomness": "{}"}}"#, randomness),
+        }
+    }
+
+    fn signed_randomness() -> String {
+        hex::encode(Sha256::digest(hex::decode(SIGNATURE).unwrap()))
+    }
+
+    #[test]
+    fn test_parse_drand_round() {
+        let beacon = Beacon::parse(&beacon_json(&signed_randomness(), Some(SIGNATURE))).unwrap();
+        assert_eq!(beacon.round, 42);
+        assert_eq!(beacon.randomness, signed_randomness());
+        assert!(beacon.validate().is_ok());
+    }
+
+    #[test]
+    fn test_parse_without_signature() {
+        let beacon = Beacon::parse(&beacon_json(&"AB".repeat(32), None)).unwrap();
+        assert_eq!(beacon.randomness, "ab".repeat(32));
+    }
+
+    #[test]
+    fn test_parse_rejects_mismatched_signature() {
+        let err = Beacon::parse(&beacon_json(&"00".repeat(32), Some(SIGNATURE))).unwrap_err();
+        assert!(err.contains("signature"), "Unexpected error: {}", err);
+    }
+
+    #[test]
+    fn test_parse_rejects_short_randomness() {
+        assert!(Beacon::parse(&beacon_json("abcd", None)).is_err());
+        assert!(Beacon::parse("not json").is_err());
+    }
+}
@@ -0,0 +1,259 @@
+use crate::luck::sorted_by_score;
+use crate::output::OmikujiResult;
+use resvg::{tiny_skia, usvg};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+const CARD_WIDTH: u32 = 600;
+const CARD_HEIGHT: u32 = 360;
+const PNG_SCALE: f32 = 2.0;
+const TOP_SCORES: usize = 5;
+const BAR_MAX_WIDTH: u32 = 150;
+const SANS: &str = "DejaVu Sans, Arial, sans-serif";
+const MONO: &str = "DejaVu Sans Mono, Courier New, monospace";
+
+/// Fonts bundled so PNG cards render the same everywhere (see assets/fonts/LICENSE).
+const FONTS: [&[u8]; 3] = [
+    include_bytes!("../assets/fonts/DejaVuSans.ttf"),
+    include_bytes!("../assets/fonts/DejaVuSans-Bold.ttf"),
+    include_bytes!("../assets/fonts/DejaVuSansMono.ttf"),
+];
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn parse_hex_color(color: &str) -> (u8, u8, u8) {
+    let channel = |i: usize| {
+        color
+            .get(i..i + 2)
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            .unwrap_or(0)
+    };
+    (channel(1), channel(3), channel(5))
+}
+
+/// Dark or light ink, whichever reads better on `background`.
+fn ink_for(background: &str) -> &'static str {
+    let (r, g, b) = parse_hex_color(background);
+    let luma = 299 * r as u32 + 587 * g as u32 + 114 * b as u32;
+    if luma > 140_000 {
+        "#1A1A1A"
+    } else {
+        "#FFFFFF"
+    }
+}
+
+/// Renders the fortune as a fixed-layout SVG card themed with `lucky_color`.
+pub fn render_svg(result: &OmikujiResult) -> String {
+    let color = &result.lucky_color;
+    let header_ink = ink_for(color);
+    let mut svg = String::new();
+
+    svg.push_str(&format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n",
+        w = CARD_WIDTH,
+        h = CARD_HEIGHT
+    ));
+    svg.push_str(&format!(
+        "  <rect width=\"{}\" height=\"{}\" rx=\"24\" fill=\"#FBF8F1\"/>\n",
+        CARD_WIDTH, CARD_HEIGHT
+    ));
+    svg.push_str(&format!(
+        "  <rect x=\"8\" y=\"8\" width=\"584\" height=\"56\" rx=\"18\" fill=\"{}\"/>\n",
+        color
+    ));
+    svg.push_str(&format!(
+        "  <rect x=\"8\" y=\"40\" width=\"584\" height=\"24\" fill=\"{}\"/>\n",
+        color
+    ));
+    svg.push_str(&format!(
+        "  <rect x=\"8\" y=\"8\" width=\"584\" height=\"344\" rx=\"18\" fill=\"none\" stroke=\"{}\" stroke-width=\"6\"/>\n",
+        color
+    ));
+    svg.push_str(&format!(
+        "  <text x=\"300\" y=\"46\" text-anchor=\"middle\" font-family=\"{}\" font-size=\"26\" font-weight=\"bold\" fill=\"{}\">Hash-Omikuji {}{}</text>\n",
+        SANS, header_ink, result.year, escape_xml(&result.namespace_suffix())
+    ));
+
+    // Left column: emoji and headline lucky values
+    svg.push_str(&format!(
+        "  <text x=\"120\" y=\"165\" text-anchor=\"middle\" font-size=\"84\">{}</text>\n",
+        escape_xml(&result.lucky_emoji)
+    ));
+    let facts = [
+        format!("Number {}  {}", result.lucky_number, result.lucky_hex),
+        format!("Color {}", result.lucky_color),
+        format!("Day {}", result.lucky_day),
+        format!("Time {}", result.lucky_time),
+    ];
+    for (i, fact) in facts.iter().enumerate() {
+        svg.push_str(&format!(
+            "  <text x=\"120\" y=\"{}\" text-anchor=\"middle\" font-family=\"{}\" font-size=\"14\" fill=\"#333333\">{}</text>\n",
+            205 + i * 22,
+            SANS,
+            escape_xml(fact)
+        ));
+    }
+
+    // Right column: top luck scores as bars
+    svg.push_str(&format!(
+        "  <text x=\"260\" y=\"100\" font-family=\"{}\" font-size=\"16\" font-weight=\"bold\" fill=\"#333333\">Top Luck</text>\n",
+        SANS
+    ));
+    for (i, score) in sorted_by_score(&result.luck_scores).into_iter().take(TOP_SCORES).enumerate() {
+        let y = 130 + i as u32 * 36;
+        let bar_width = score.score.min(100) as u32 * BAR_MAX_WIDTH / 100;
+        svg.push_str(&format!(
+            "  <text x=\"260\" y=\"{}\" font-family=\"{}\" font-size=\"14\" fill=\"#333333\">{}</text>\n",
+            y,
+            SANS,
+            escape_xml(score.luck_type.name())
+        ));
+        svg.push_str(&format!(
+            "  <rect x=\"390\" y=\"{}\" width=\"{}\" height=\"14\" rx=\"4\" fill=\"#E6E1D6\"/>\n",
+            y - 12,
+            BAR_MAX_WIDTH
+        ));
+        svg.push_str(&format!(
+            "  <rect x=\"390\" y=\"{}\" width=\"{}\" height=\"14\" rx=\"4\" fill=\"{}\"/>\n",
+            y - 12,
+            bar_width,
+            color
+        ));
+        svg.push_str(&format!(
+            "  <text x=\"572\" y=\"{}\" text-anchor=\"end\" font-family=\"{}\" font-size=\"14\" fill=\"#333333\">{}</text>\n",
+            y, SANS, score.score
+        ));
+    }
+
+    // Footer: fingerprint split over two lines
+    let (fp_head, fp_tail) = result.fingerprint.split_at(result.fingerprint.len() / 2);
+    for (i, part) in [fp_head, fp_tail].iter().enumerate() {
+        svg.push_str(&format!(
+            "  <text x=\"300\" y=\"{}\" text-anchor=\"middle\" font-family=\"{}\" font-size=\"12\" fill=\"#777777\">{}</text>\n",
+            318 + i * 16,
+            MONO,
+            escape_xml(part)
+        ));
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Font database holding only the bundled fonts.
+fn bundled_fonts() -> usvg::fontdb::Database {
+    let mut fontdb = usvg::fontdb::Database::new();
+    for font in FONTS {
+        fontdb.load_font_data(font.to_vec());
+    }
+    fontdb.set_sans_serif_family("DejaVu Sans");
+    fontdb.set_monospace_family("DejaVu Sans Mono");
+    fontdb
+}
+
+fn rasterize(svg: &str, fontdb: usvg::fontdb::Database) -> Result<Vec<u8>, String> {
+    let options = usvg::Options {
+        font_family: "DejaVu Sans".to_string(),
+        fontdb: Arc::new(fontdb),
+        ..Default::default()
+    };
+    let tree = usvg::Tree::from_str(svg, &options).map_err(|e| e.to_string())?;
+
+    let width = (CARD_WIDTH as f32 * PNG_SCALE) as u32;
+    let height = (CARD_HEIGHT as f32 * PNG_SCALE) as u32;
+    let mut pixmap = tiny_skia::Pixmap::new(width, height).ok_or("Failed to allocate image")?;
+    resvg::render(&tree, tiny_skia::Transform::from_scale(PNG_SCALE, PNG_SCALE), &mut pixmap.as_mut());
+    pixmap.encode_png().map_err(|e| e.to_string())
+}
+
+/// Rasterizes the SVG card with resvg (pure Rust) using the bundled fonts.
+pub fn render_png(result: &OmikujiResult) -> Result<Vec<u8>, String> {
+    rasterize(&render_svg(result), bundled_fonts())
+}
+
+/// Writes the card to `path`, choosing SVG or PNG by file extension.
+pub fn write_card(result: &OmikujiResult, path: &Path) -> Result<(), String> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+    let bytes = match extension.as_deref() {
+        Some("svg") => render_svg(result).into_bytes(),
+        Some("png") => render_png(result)?,
+        _ => return Err(format!("Unsupported card format: {} (use .svg or .png)", path.display())),
+    };
+    fs::write(path, bytes).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::HashBits;
+
+    fn create_test_result() -> OmikujiResult {
+        let hash = HashBits::from_seed(2026, "test-user");
+        OmikujiResult::from_hash(&hash, 2026, "test-user")
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(escape_xml("<a & \"b\">"), "&lt;a &amp; &quot;b&quot;&gt;");
+    }
+
+    #[test]
+    fn test_ink_contrast() {
+        assert_eq!(ink_for("#FFFFFF"), "#1A1A1A");
+        assert_eq!(ink_for("#000000"), "#FFFFFF");
+    }
+
+    #[test]
+    fn test_svg_uses_lucky_color_and_fingerprint() {
+        let result = create_test_result();
+        let svg = render_svg(&result);
+        assert!(svg.contains(&result.lucky_color));
+        assert!(svg.contains(&result.fingerprint[..32]));
+        assert!(svg.contains(&result.fingerprint[32..]));
+    }
+
+    #[test]
+    fn test_svg_parses() {
+        let result = create_test_result();
+        let tree = usvg::Tree::from_str(&render_svg(&result), &usvg::Options::default());
+        assert!(tree.is_ok());
+    }
+
+    #[test]
+    fn test_png_signature() {
+        let result = create_test_result();
+        let png = render_png(&result).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    }
+
+    #[test]
+    fn test_bundled_fonts_draw_text() {
+        let svg = render_svg(&create_test_result());
+        let fontdb = bundled_fonts();
+        assert!(fontdb.faces().any(|face| face.families.iter().any(|(name, _)| name == "DejaVu Sans")));
+        let with_text = rasterize(&svg, fontdb).unwrap();
+        let without_text = rasterize(&svg, usvg::fontdb::Database::new()).unwrap();
+        assert_ne!(with_text, without_text);
+    }
+
+    #[test]
+    fn test_write_card_rejects_unknown_extension() {
+        let result = create_test_result();
+        assert!(write_card(&result, Path::new("card.gif")).is_err());
+    }
+
+    #[test]
+    fn test_snapshot_svg_card() {
+        let result = create_test_result();
+        insta::assert_snapshot!(render_svg(&result));
+    }
+}
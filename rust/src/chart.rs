@@ -0,0 +1,177 @@
+use crate::luck::{sorted_by_score, LuckScore, LuckTheme};
+use clap::ValueEnum;
+use std::f64::consts::PI;
+
+const BAR_WIDTH: usize = 20;
+const PARTIAL_BLOCKS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+const RADAR_RADIUS: usize = 8;
+const RADAR_RING_STEPS: usize = 96;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ScoreStyle {
+    /// Numbers with rank, e.g. "95 (Excellent)"
+    #[default]
+    Plain,
+    /// Horizontal bar chart
+    Bars,
+    /// ASCII radar chart with a legend
+    Radar,
+}
+
+/// Horizontal bar for a 0-100 score, drawn with 1/8 block precision.
+pub fn score_bar(score: u8) -> String {
+    let eighths = score.min(100) as usize * BAR_WIDTH * 8 / 100;
+    let partial = eighths % 8;
+    let mut bar = "█".repeat(eighths / 8);
+    if partial > 0 {
+        bar.push(PARTIAL_BLOCKS[partial]);
+    }
+    format!("{:<width$}", bar, width = BAR_WIDTH)
+}
+
+fn score_line(score: &LuckScore, style: ScoreStyle) -> String {
+    match style {
+        ScoreStyle::Bars => format!(
+            "{:18}: {} {:3} ({})\n",
+            score.luck_type.name(),
+            score_bar(score.score),
+            score.score,
+            score.rank.as_str()
+        ),
+        ScoreStyle::Plain | ScoreStyle::Radar => format!(
+            "{:18}: {:3} ({})\n",
+            score.luck_type.name(),
+            score.score,
+            score.rank.as_str()
+        ),
+    }
+}
+
+/// Radar chart: one axis per luck type, clockwise from the top.
+/// Rings mark scores 50 and 100; each score is plotted as a letter
+/// A, B, C... in `scores` order (see legend).
+pub fn radar_chart(scores: &[LuckScore]) -> String {
+    let rows = RADAR_RADIUS * 2 + 1;
+    // Terminal cells are about twice as tall as wide, so stretch x by 2.
+    let cols = RADAR_RADIUS * 4 + 1;
+    let mut grid = vec![vec![' '; cols]; rows];
+    let center = RADAR_RADIUS as f64;
+
+    let plot = |grid: &mut Vec<Vec<char>>, angle: f64, radius: f64, ch: char| {
+        let x = (center * 2.0 + radius * angle.cos() * 2.0).round() as usize;
+        let y = (center + radius * angle.sin()).round() as usize;
+        grid[y][x] = ch;
+    };
+
+    let axis_angle = |i: usize| -PI / 2.0 + 2.0 * PI * i as f64 / scores.len() as f64;
+
+    for step in 0..RADAR_RING_STEPS {
+        let angle = 2.0 * PI * step as f64 / RADAR_RING_STEPS as f64;
+        plot(&mut grid, angle, RADAR_RADIUS as f64, '·');
+        plot(&mut grid, angle, RADAR_RADIUS as f64 / 2.0, '·');
+    }
+    grid[RADAR_RADIUS][RADAR_RADIUS * 2] = '+';
+    for (i, score) in scores.iter().enumerate() {
+        let radius = score.score.min(100) as f64 / 100.0 * RADAR_RADIUS as f64;
+        plot(&mut grid, axis_angle(i), radius, (b'A' + i as u8) as char);
+    }
+
+    let mut output = String::new();
+    for row in grid {
+        let line: String = row.into_iter().collect();
+        output.push_str(line.trim_end());
+        output.push('\n');
+    }
+    output.push('\n');
+    for (i, score) in scores.iter().enumerate() {
+        output.push_str(&format!("{} {}", (b'A' + i as u8) as char, score_line(score, ScoreStyle::Plain)));
+    }
+    output
+}
+
+/// Renders the body of the "Luck Scores" block.
+/// Scores are sorted best first; `limit` keeps only the top N.
+/// The radar style always plots every score in `LuckType::ALL` order.
+pub fn render_scores(scores: &[LuckScore], style: ScoreStyle, grouped: bool, limit: Option<usize>) -> String {
+    if style == ScoreStyle::Radar {
+        return radar_chart(scores);
+    }
+
+    let mut sorted = sorted_by_score(scores);
+    if let Some(limit) = limit {
+        sorted.truncate(limit);
+    }
+
+    if !grouped {
+        return sorted.iter().map(|score| score_line(score, style)).collect();
+    }
+
+    let mut output = String::new();
+    for theme in LuckTheme::ALL {
+        let in_theme: Vec<_> = sorted.iter().filter(|score| score.luck_type.theme() == theme).collect();
+        if in_theme.is_empty() {
+            continue;
+        }
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        output.push_str(&format!("[{}]\n", theme.name()));
+        for score in in_theme {
+            output.push_str(&score_line(score, style));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::luck::{LuckScore, LuckType};
+
+    #[test]
+    fn test_score_bar_width() {
+        for score in [0, 1, 33, 50, 99, 100] {
+            assert_eq!(score_bar(score).chars().count(), BAR_WIDTH);
+        }
+    }
+
+    #[test]
+    fn test_score_bar_extremes() {
+        assert_eq!(score_bar(0), " ".repeat(BAR_WIDTH));
+        assert_eq!(score_bar(100), "█".repeat(BAR_WIDTH));
+    }
+
+    #[test]
+    fn test_score_bar_partial_block() {
+        // 33% of 160 eighths = 52 eighths -> 6 full blocks + 4/8 block
+        assert!(score_bar(33).starts_with("██████▌ "));
+    }
+
+    #[test]
+    fn test_render_scores_limit() {
+        let scores: Vec<_> = LuckType::ALL.iter().map(|&t| LuckScore::new(t, 128)).collect();
+        let text = render_scores(&scores, ScoreStyle::Plain, false, Some(5));
+        assert_eq!(text.lines().count(), 5);
+    }
+
+    #[test]
+    fn test_render_scores_grouped_has_theme_headers() {
+        let scores: Vec<_> = LuckType::ALL.iter().map(|&t| LuckScore::new(t, 128)).collect();
+        let text = render_scores(&scores, ScoreStyle::Bars, true, None);
+        for theme in LuckTheme::ALL {
+            assert!(text.contains(&format!("[{}]", theme.name())));
+        }
+    }
+
+    #[test]
+    fn test_radar_chart_has_all_markers() {
+        let scores: Vec<_> = LuckType::ALL.iter().map(|&t| LuckScore::new(t, 255)).collect();
+        let chart = radar_chart(&scores);
+        let (grid, legend) = chart.split_once("\n\n").unwrap();
+        for i in 0..16 {
+            let marker = (b'A' + i) as char;
+            assert!(grid.contains(marker), "Missing marker: {}", marker);
+        }
+        assert_eq!(legend.lines().count(), 16);
+    }
+}
@@ -0,0 +1,394 @@
+use crate::chart::score_bar;
+use crate::luck::sorted_by_score;
+use crate::output::OmikujiResult;
+use serde_json::{json, Value};
+
+const TOP_SCORES: usize = 5;
+
+// Limits the Slack and Discord APIs enforce on incoming messages
+const SLACK_MAX_HEADER: usize = 150;
+const SLACK_MAX_FIELDS: usize = 10;
+const SLACK_MAX_FIELD_TEXT: usize = 2000;
+const DISCORD_MAX_TITLE: usize = 256;
+const DISCORD_MAX_DESCRIPTION: usize = 4096;
+const DISCORD_MAX_FIELDS: usize = 25;
+const DISCORD_MAX_FIELD_NAME: usize = 256;
+const DISCORD_MAX_FIELD_VALUE: usize = 1024;
+const DISCORD_MAX_FOOTER: usize = 2048;
+const DISCORD_MAX_COLOR: u64 = 0xFFFFFF;
+const DISCORD_MAX_TOTAL: usize = 6000;
+
+fn title(result: &OmikujiResult) -> String {
+    format!("🎍 Hash-Omikuji {}{} 🎍", result.year, result.namespace_suffix())
+}
+
+fn lucky_fields(result: &OmikujiResult) -> Vec<(&'static str, String)> {
+    vec![
+        ("Lucky Number", result.lucky_number.to_string()),
+        ("Lucky Color", result.lucky_color.clone()),
+        ("Lucky Day", result.lucky_day.clone()),
+        ("Lucky Time", result.lucky_time.clone()),
+        ("Lucky Emoji", result.lucky_emoji.clone()),
+        ("Lucky Direction", result.lucky_direction.clone()),
+        ("Lucky Element", result.lucky_element.clone()),
+        ("Lucky Percent", format!("{}%", result.lucky_percent)),
+    ]
+}
+
+/// One line per top score: "`████▌   ` 96 Debug Luck (Excellent)".
+fn top_scores(result: &OmikujiResult) -> String {
+    sorted_by_score(&result.luck_scores)
+        .into_iter()
+        .take(TOP_SCORES)
+        .map(|s| format!("`{}` {:>3} {} ({})", score_bar(s.score), s.score, s.luck_type.name(), s.rank.as_str()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn footer(result: &OmikujiResult) -> String {
+    format!("{} | {}", result.seed, result.entropy_check)
+}
+
+/// Slack message with Block Kit blocks inside an attachment, so the
+/// sidebar takes the lucky color.
+pub fn slack_blocks(result: &OmikujiResult) -> Value {
+    let fields: Vec<Value> = lucky_fields(result)
+        .into_iter()
+        .map(|(name, value)| json!({ "type": "mrkdwn", "text": format!("*{}*\n{}", name, value) }))
+        .collect();
+    json!({
+        "text": format!("{} {}", title(result), result.lucky_emoji),
+        "attachments": [{
+            "color": result.lucky_color,
+            "blocks": [
+                { "type": "header", "text": { "type": "plain_text", "text": title(result), "emoji": true } },
+                { "type": "section", "fields": fields },
+                { "type": "section", "text": { "type": "mrkdwn", "text": format!("*Top luck*\n{}", top_scores(result)) } },
+                { "type": "context", "elements": [{ "type": "mrkdwn", "text": footer(result) }] },
+            ],
+        }],
+    })
+}
+
+/// Discord webhook message with a single embed colored with the lucky color.
+pub fn discord_embed(result: &OmikujiResult) -> Value {
+    let color = u32::from_str_radix(result.lucky_color.trim_start_matches('#'), 16).unwrap_or(0);
+    let mut fields: Vec<Value> = lucky_fields(result)
+        .into_iter()
+        .map(|(name, value)| json!({ "name": name, "value": value, "inline": true }))
+        .collect();
+    fields.push(json!({ "name": "Top luck", "value": top_scores(result), "inline": false }));
+    json!({
+        "embeds": [{
+            "title": title(result),
+            "description": format!("{} Lucky number **{}**", result.lucky_emoji, result.lucky_number),
+            "color": color,
+            "fields": fields,
+            "footer": { "text": footer(result) },
+        }],
+    })
+}
+
+fn check_len(what: &str, text: &str, max: usize) -> Result<(), String> {
+    let len = text.chars().count();
+    if len > max {
+        return Err(format!("{} is {} chars (limit {})", what, len, max));
+    }
+    Ok(())
+}
+
+/// Checks a Slack payload against the Block Kit limits.
+pub fn validate_slack(payload: &Value) -> Result<(), String> {
+    let color = payload["attachments"][0]["color"].as_str().unwrap_or("");
+    let hex = color.strip_prefix('#').unwrap_or("");
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Slack attachment color {:?} is not #RRGGBB", color));
+    }
+    let blocks = payload["attachments"][0]["blocks"].as_array().ok_or("Slack payload has no blocks")?;
+    for block in blocks {
+        match block["type"].as_str() {
+            Some("header") => check_len("Slack header", block["text"]["text"].as_str().unwrap_or(""), SLACK_MAX_HEADER)?,
+            Some("section") => {
+                let fields = block["fields"].as_array().map(Vec::as_slice).unwrap_or_default();
+                if fields.len() > SLACK_MAX_FIELDS {
+                    return Err(format!("Slack section has {} fields (limit {})", fields.len(), SLACK_MAX_FIELDS));
+                }
+                for text in fields.iter().chain([&block["text"]]).filter_map(|f| f["text"].as_str()) {
+                    check_len("Slack text", text, SLACK_MAX_FIELD_TEXT)?;
+                }
+            }
+            Some("context") => {}
+            other => return Err(format!("Unexpected Slack block type: {:?}", other)),
+        }
+    }
+    Ok(())
+}
+
+/// Checks a Discord payload against the embed limits.
+pub fn validate_discord(payload: &Value) -> Result<(), String> {
+    let embed = &payload["embeds"][0];
+    let title = embed["title"].as_str().ok_or("Discord payload has no embed")?;
+    check_len("Discord title", title, DISCORD_MAX_TITLE)?;
+    check_len("Discord description", embed["description"].as_str().unwrap_or(""), DISCORD_MAX_DESCRIPTION)?;
+    check_len("Discord footer", embed["footer"]["text"].as_str().unwrap_or(""), DISCORD_MAX_FOOTER)?;
+    if embed["color"].as_u64().is_none_or(|color| color > DISCORD_MAX_COLOR) {
+        return Err(format!("Discord color {} is not an integer RGB value", embed["color"]));
+    }
+    let fields = embed["fields"].as_array().map(Vec::as_slice).unwrap_or_default();
+    if fields.len() > DISCORD_MAX_FIELDS {
+        return Err(format!("Discord embed has {} fields (limit {})", fields.len(), DISCORD_MAX_FIELDS));
+    }
+    let mut total = title.chars().count();
+    for field in fields {
+        let value = field["value"].as_str().unwrap_or("");
+        check_len("Discord field name", field["name"].as_str().unwrap_or(""), DISCORD_MAX_FIELD_NAME)?;
+        check_len("Discord field value", value, DISCORD_MAX_FIELD_VALUE)?;
+        total += value.chars().count() + field["name"].as_str().unwrap_or("").chars().count();
+    }
+    for text in [&embed["description"], &embed["footer"]["text"]].into_iter().filter_map(Value::as_str) {
+        total += text.chars().count();
+    }
+    if total > DISCORD_MAX_TOTAL {
+        return Err(format!("Discord embed is {} chars (limit {})", total, DISCORD_MAX_TOTAL));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::HashBits;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::thread;
+
+    fn create_test_result() -> OmikujiResult {
+        let hash = HashBits::from_seed(2026, "test-user");
+        OmikujiResult::from_hash(&hash, 2026, "test-user")
+    }
+
+    /// Known-good webhook payloads written from the Slack Block Kit and
+    /// Discord embed documentation, independent of the code under test.
+    const SLACK_KNOWN_GOOD: &str = include_str!("../testdata/slack_webhook.json");
+    const DISCORD_KNOWN_GOOD: &str = include_str!("../testdata/discord_webhook.json");
+
+    fn chars(value: &Value) -> usize {
+        value.as_str().map_or(0, |text| text.chars().count())
+    }
+
+    /// `Ok` if every key, block type and JSON type in `value` also appears at
+    /// the same place in `known`.
+    fn same_shape(value: &Value, known: &Value, path: &str) -> Result<(), String> {
+        match (value, known) {
+            (Value::Object(map), Value::Object(known_map)) => {
+                for (key, item) in map {
+                    let path = format!("{}.{}", path, key);
+                    let known_item = known_map.get(key).ok_or_else(|| format!("{} is not in the known-good payload", path))?;
+                    if key == "type" && item != known_item {
+                        return Err(format!("{} is {}, expected {}", path, item, known_item));
+                    }
+                    same_shape(item, known_item, &path)?;
+                }
+                Ok(())
+            }
+            (Value::Array(items), Value::Array(known_items)) => {
+                for (i, item) in items.iter().enumerate() {
+                    let path = format!("{}[{}]", path, i);
+                    if !known_items.iter().any(|known_item| same_shape(item, known_item, &path).is_ok()) {
+                        return Err(format!("{} matches no known-good element", path));
+                    }
+                }
+                Ok(())
+            }
+            (Value::String(_), Value::String(_)) | (Value::Bool(_), Value::Bool(_)) => Ok(()),
+            (Value::Number(_), Value::Number(_)) => Ok(()),
+            _ => Err(format!("{} has the wrong JSON type", path)),
+        }
+    }
+
+    /// What Slack's incoming webhook accepts, spelled out from the API docs.
+    fn slack_accepts(payload: &Value) -> Result<(), String> {
+        same_shape(payload, &serde_json::from_str(SLACK_KNOWN_GOOD).unwrap(), "$")?;
+        if chars(&payload["text"]) == 0 {
+            return Err("missing fallback text".to_string());
+        }
+        let attachment = &payload["attachments"][0];
+        let color = attachment["color"].as_str().unwrap_or("");
+        if color.len() != 7 || !color.starts_with('#') || !color[1..].chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("bad color {:?}", color));
+        }
+        let blocks = attachment["blocks"].as_array().ok_or("missing blocks")?;
+        if blocks.is_empty() || blocks.len() > 50 {
+            return Err(format!("{} blocks", blocks.len()));
+        }
+        for block in blocks {
+            let fits = match block["type"].as_str() {
+                Some("header") => (1..=150).contains(&chars(&block["text"]["text"])),
+                Some("section") => {
+                    let fields = block["fields"].as_array().map(Vec::as_slice).unwrap_or_default();
+                    (block["text"].is_object() || !fields.is_empty())
+                        && chars(&block["text"]["text"]) <= 3000
+                        && fields.len() <= 10
+                        && fields.iter().all(|field| (1..=2000).contains(&chars(&field["text"])))
+                }
+                Some("context") => block["elements"].as_array().is_some_and(|e| (1..=10).contains(&e.len())),
+                _ => false,
+            };
+            if !fits {
+                return Err(format!("block outside the Block Kit limits: {}", block));
+            }
+        }
+        Ok(())
+    }
+
+    /// What Discord's webhook endpoint accepts, spelled out from the API docs.
+    fn discord_accepts(payload: &Value) -> Result<(), String> {
+        same_shape(payload, &serde_json::from_str(DISCORD_KNOWN_GOOD).unwrap(), "$")?;
+        let embeds = payload["embeds"].as_array().ok_or("missing embeds")?;
+        if embeds.is_empty() || embeds.len() > 10 {
+            return Err(format!("{} embeds", embeds.len()));
+        }
+        for embed in embeds {
+            let fields = embed["fields"].as_array().map(Vec::as_slice).unwrap_or_default();
+            let total = chars(&embed["title"])
+                + chars(&embed["description"])
+                + chars(&embed["footer"]["text"])
+                + fields.iter().map(|f| chars(&f["name"]) + chars(&f["value"])).sum::<usize>();
+            let fits = chars(&embed["title"]) <= 256
+                && chars(&embed["description"]) <= 4096
+                && chars(&embed["footer"]["text"]) <= 2048
+                && embed["color"].as_u64().is_some_and(|color| color <= 0xFFFFFF)
+                && fields.len() <= 25
+                && fields.iter().all(|f| (1..=256).contains(&chars(&f["name"])) && (1..=1024).contains(&chars(&f["value"])))
+                && total <= 6000;
+            if !fits {
+                return Err(format!("embed outside the Discord limits: {}", embed));
+            }
+        }
+        Ok(())
+    }
+
+    /// Local stand-in for a chat webhook: accepts one POST, checks the
+    /// payload with `accepts`, and returns the HTTP status together with
+    /// the payload as received.
+    fn replay(payload: &Value, accepts: fn(&Value) -> Result<(), String>) -> (u16, Value) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let addr = server.server_addr().to_ip().unwrap();
+        let webhook = thread::spawn(move || {
+            let mut request = server.recv().unwrap();
+            let mut body = String::new();
+            request.as_reader().read_to_string(&mut body).unwrap();
+            let received: Value = serde_json::from_str(&body).unwrap();
+            let status = if accepts(&received).is_ok() { 200 } else { 400 };
+            request.respond(tiny_http::Response::empty(status)).unwrap();
+            received
+        });
+
+        let body = payload.to_string();
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "POST /webhook HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            addr,
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let status = response.split_whitespace().nth(1).unwrap().parse().unwrap();
+        (status, webhook.join().unwrap())
+    }
+
+    #[test]
+    fn test_slack_sidebar_color_and_top_scores() {
+        let payload = slack_blocks(&create_test_result());
+        assert_eq!(payload["attachments"][0]["color"], "#E35F21");
+        let top = payload["attachments"][0]["blocks"][2]["text"]["text"].as_str().unwrap();
+        assert_eq!(top.lines().count(), 1 + TOP_SCORES);
+        assert!(top.contains("Debug Luck (Excellent)"));
+    }
+
+    #[test]
+    fn test_discord_color_is_integer() {
+        let payload = discord_embed(&create_test_result());
+        assert_eq!(payload["embeds"][0]["color"], 0xE35F21);
+    }
+
+    #[test]
+    fn test_validators_enforce_limits() {
+        let mut slack = slack_blocks(&create_test_result());
+        slack["attachments"][0]["blocks"][0]["text"]["text"] = json!("x".repeat(SLACK_MAX_HEADER + 1));
+        assert!(validate_slack(&slack).is_err());
+
+        let mut discord = discord_embed(&create_test_result());
+        discord["embeds"][0]["fields"][0]["value"] = json!("x".repeat(DISCORD_MAX_FIELD_VALUE + 1));
+        assert!(validate_discord(&discord).is_err());
+    }
+
+    #[test]
+    fn test_validators_check_colors() {
+        let mut slack = slack_blocks(&create_test_result());
+        slack["attachments"][0]["color"] = json!("E35F21");
+        assert!(validate_slack(&slack).is_err());
+
+        let mut discord = discord_embed(&create_test_result());
+        discord["embeds"][0]["color"] = json!("#E35F21");
+        assert!(validate_discord(&discord).is_err());
+    }
+
+    #[test]
+    fn test_webhook_stub_accepts_known_good_payloads() {
+        for (known, accepts) in [
+            (SLACK_KNOWN_GOOD, slack_accepts as fn(&Value) -> Result<(), String>),
+            (DISCORD_KNOWN_GOOD, discord_accepts),
+        ] {
+            let payload: Value = serde_json::from_str(known).unwrap();
+            assert_eq!(replay(&payload, accepts).0, 200);
+        }
+    }
+
+    #[test]
+    fn test_webhook_replay_slack() {
+        let payload = slack_blocks(&create_test_result());
+        let (status, received) = replay(&payload, slack_accepts);
+        assert_eq!(status, 200);
+        assert_eq!(received, payload);
+    }
+
+    #[test]
+    fn test_webhook_replay_discord() {
+        let payload = discord_embed(&create_test_result());
+        let (status, received) = replay(&payload, discord_accepts);
+        assert_eq!(status, 200);
+        assert_eq!(received, payload);
+    }
+
+    #[test]
+    fn test_webhook_replay_rejects_invalid_payload() {
+        let (status, _) = replay(&json!({ "embeds": [] }), discord_accepts);
+        assert_eq!(status, 400);
+
+        let mut slack = slack_blocks(&create_test_result());
+        slack["attachments"][0]["blocks"][0]["text"]["text"] = json!("x".repeat(151));
+        assert_eq!(replay(&slack, slack_accepts).0, 400);
+
+        let mut discord = discord_embed(&create_test_result());
+        discord["embeds"][0]["color"] = json!("#E35F21");
+        assert_eq!(replay(&discord, discord_accepts).0, 400);
+
+        discord["embeds"][0]["color"] = json!(0xE35F21);
+        discord["embeds"][0]["fields"][0]["inline"] = json!("true");
+        assert_eq!(replay(&discord, discord_accepts).0, 400);
+    }
+
+    #[test]
+    fn test_snapshot_slack_blocks() {
+        insta::assert_json_snapshot!(slack_blocks(&create_test_result()));
+    }
+
+    #[test]
+    fn test_snapshot_discord_embed() {
+        insta::assert_json_snapshot!(discord_embed(&create_test_result()));
+    }
+}
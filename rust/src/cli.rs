@@ -1,13 +1,15 @@
-use clap::Parser;
+use crate::chart::ScoreStyle;
+use crate::config::Config;
+use crate::format::OutputFormat;
+use crate::hash::{self, HashScheme};
+use crate::privacy::{FingerprintFormat, SeedDisplay};
+use crate::schema::SchemaVersion;
+use crate::normalize::{self, Normalization};
+use crate::seed::{self, SeedSource};
+use clap::{Parser, Subcommand};
 use chrono::{Datelike, Local};
-use gethostname::gethostname;
-use std::env;
-
-fn get_default_seed() -> String {
-    let hostname = gethostname().to_string_lossy().to_string();
-    let username = env::var("USER").unwrap_or_else(|_| "anonymous".to_string());
-    format!("{}@{}", username, hostname)
-}
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
 #[command(name = "hash-omikuji")]
@@ -16,40 +18,253 @@ fn get_default_seed() -> String {
 #[command(about = "SHA-256 based deterministic fortune telling CLI")]
 #[command(long_about = "A deterministic 'omikuji' (fortune slip) generator using SHA-256.\nThis command can only be executed on January 1st.\nSame input always produces the same result.")]
 pub struct Args {
-    /// Force execution for a specific year (bypasses January 1st restriction)
-    #[arg(long)]
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Force execution for a specific year, 1-9999 (bypasses January 1st restriction)
+    #[arg(long, global = true, value_parser = clap::value_parser!(u32).range(1..=9999))]
     pub force_year: Option<u32>,
 
-    /// Custom seed string (defaults to username@hostname)
-    #[arg(long, short)]
+    /// Custom seed string (defaults to the --seed-source value)
+    #[arg(long, short, global = true)]
     pub seed: Option<String>,
 
-    /// Output as JSON
-    #[arg(long, default_value_t = false)]
+    /// Where the default seed comes from
+    #[arg(long, value_enum, default_value_t = SeedSource::UserHost, conflicts_with = "seed", global = true)]
+    pub seed_source: SeedSource,
+
+    /// Normalize the seed before hashing, e.g. --normalize nfc,trim,casefold,email
+    #[arg(long, value_enum, value_delimiter = ',', global = true)]
+    pub normalize: Vec<Normalization>,
+
+    /// Hash input encoding (2 is unambiguous but gives different fortunes)
+    #[arg(long, value_enum, default_value_t = HashScheme::V1, global = true)]
+    pub scheme: HashScheme,
+
+    /// Namespace mixed into the derivation, so each organization gets its
+    /// own fortunes (overrides `namespace` in config.toml; "" for none)
+    #[arg(long, global = true)]
+    pub namespace: Option<String>,
+
+    /// drand-style beacon JSON ({"round", "randomness"}) to mix into the
+    /// derivation, so fortunes can't be computed before that round ("-" for stdin)
+    #[arg(long, global = true)]
+    pub beacon_file: Option<PathBuf>,
+
+    /// Derive the fortune with HMAC-SHA256 using a local secret key
+    /// (created in the config dir on first use)
+    #[arg(long, default_value_t = false, global = true)]
+    pub private: bool,
+
+    /// Bytes of the seed fingerprint to display (1-32)
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u8).range(1..=32), global = true)]
+    pub fingerprint_len: u8,
+
+    /// Encoding of the displayed seed fingerprint
+    #[arg(long, value_enum, default_value_t = FingerprintFormat::Hex, global = true)]
+    pub fingerprint_format: FingerprintFormat,
+
+    /// Output as JSON (same as --format json)
+    #[arg(long, default_value_t = false, conflicts_with = "format", global = true)]
     pub json: bool,
 
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text, global = true)]
+    pub format: OutputFormat,
+
+    /// JSON layout version (output defaults to 1, `schema` to 2)
+    #[arg(long, value_enum, global = true)]
+    pub schema_version: Option<SchemaVersion>,
+
     /// Show only top 5 luck scores
-    #[arg(long, default_value_t = false)]
+    #[arg(long, default_value_t = false, global = true)]
     pub short: bool,
 
     /// Show seed and fingerprint in output
-    #[arg(long, default_value_t = false)]
+    #[arg(long, default_value_t = false, global = true)]
     pub show_seed: bool,
 
+    /// How to display luck scores
+    #[arg(long, value_enum, default_value_t = ScoreStyle::Plain, global = true)]
+    pub style: ScoreStyle,
+
+    /// Group luck scores by theme (life, relationships, work, tech)
+    #[arg(long, default_value_t = false, global = true)]
+    pub group: bool,
+
+    /// Also export a fortune card image (.svg or .png)
+    #[arg(long, short, global = true)]
+    pub output: Option<PathBuf>,
+
+    /// Reveal the fortune line by line (text output on a terminal only)
+    #[arg(long, default_value_t = false, global = true)]
+    pub animate: bool,
+
+    /// Show how each field was derived from the hash bits (text output only)
+    #[arg(long, default_value_t = false, global = true)]
+    pub explain: bool,
+
+    /// Draw the fingerprint as OpenSSH-style randomart
+    #[arg(long, default_value_t = false, global = true)]
+    pub art: bool,
+
+    /// Also export a symmetric identicon of the fingerprint (.svg)
+    #[arg(long, global = true)]
+    pub identicon: Option<PathBuf>,
+
     /// Override current date for testing (format: YYYY-MM-DD)
-    #[arg(long)]
+    #[arg(long, global = true)]
     pub date: Option<String>,
 }
 
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Draw your fortune (the default when no subcommand is given)
+    Draw,
+    /// Check that a fortune saved with --json was drawn from --seed
+    Verify {
+        /// Saved JSON fortune ("-" for stdin)
+        file: PathBuf,
+    },
+    /// Show how the fingerprint is computed (hash input, algorithm, digest)
+    /// and how each field is derived from its bits
+    Explain,
+    /// Print the 256-bit layout: which bits make up each field
+    Layout,
+    /// Luck score statistics over many seeds, and where your fortune ranks
+    Stats {
+        /// Number of sample seeds to draw
+        #[arg(long, default_value_t = 10_000, value_parser = clap::value_parser!(u32).range(1..))]
+        samples: u32,
+    },
+    /// Flip one bit of the seed and show how much of the fingerprint and
+    /// which fields change (the SHA-256 avalanche effect)
+    Avalanche {
+        /// Seed bit to flip, from the first byte's most significant bit
+        /// (defaults to the last bit, which changes only the last character)
+        #[arg(long)]
+        bit: Option<usize>,
+    },
+    /// Print a shell completion script (bash, zsh, fish, powershell, elvish)
+    Completions {
+        shell: clap_complete::Shell,
+    },
+    /// Print the man page, or write pages for every subcommand with --out-dir
+    Manpage {
+        /// Directory for hash-omikuji.1 and hash-omikuji-<subcommand>.1
+        #[arg(long)]
+        out_dir: Option<PathBuf>,
+    },
+    /// Print the JSON Schema of the JSON output
+    Schema,
+    /// Print a commitment to your seed and draw options (with a random
+    /// nonce) to publish before the draw
+    Commit {
+        /// Beacon round the draw will use (its randomness is not known yet)
+        #[arg(long)]
+        beacon_round: Option<u64>,
+    },
+    /// Prove your seed matches an earlier commitment, then draw the fortune
+    Reveal {
+        /// Nonce printed by `commit`
+        #[arg(long)]
+        nonce: String,
+        /// Commitment printed by `commit`
+        #[arg(long)]
+        commitment: String,
+    },
+    /// Interactive fortune draw with charts, history and a team leaderboard
+    Tui {
+        /// Team members' seeds for the leaderboard (overrides `team` in config.toml)
+        #[arg(long, value_delimiter = ',')]
+        team: Vec<String>,
+    },
+    /// Serve fortunes over HTTP (GET /fortune, /fortune.svg, /healthz; POST /verify)
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+        /// Port to listen on
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+        /// Requests allowed per client address per minute (at least 1)
+        #[arg(long, default_value_t = 60, value_parser = clap::value_parser!(u32).range(1..))]
+        rate_limit: u32,
+    },
+    /// Draw fortunes for many seeds: one seed or `seed,year` per line, as
+    /// NDJSON (default) or --format csv, in input order
+    Batch {
+        /// File with one seed per line ("-" for stdin)
+        #[arg(default_value = "-")]
+        file: PathBuf,
+        /// Worker threads (defaults to the number of CPUs)
+        #[arg(long)]
+        jobs: Option<NonZeroUsize>,
+    },
+    /// Re-render a fortune saved with --json (without recomputing it)
+    Show {
+        /// Saved JSON fortune ("-" for stdin)
+        file: PathBuf,
+    },
+}
+
+impl Default for Args {
+    /// Same values as a bare `hash-omikuji` invocation.
+    fn default() -> Self {
+        Args::parse_from(["hash-omikuji"])
+    }
+}
+
 impl Args {
-    pub fn get_seed(&self) -> String {
-        self.seed.clone().unwrap_or_else(get_default_seed)
+    /// The seed to hash, after `--normalize`, and the steps applied.
+    pub fn get_seed(&self) -> Result<(String, Vec<Normalization>), String> {
+        Ok(normalize::normalize(&self.raw_seed()?, &self.normalize))
+    }
+
+    /// `--seed`, or the `--seed-source` value, before normalization.
+    pub fn raw_seed(&self) -> Result<String, String> {
+        match self.seed {
+            Some(ref seed) => Ok(seed.clone()),
+            None => seed::resolve(self.seed_source),
+        }
+    }
+
+    /// `--namespace`, falling back to the config file. An empty name
+    /// means no namespace.
+    pub fn namespace(&self, config: &Config) -> Option<String> {
+        hash::non_empty_namespace(self.namespace.as_deref().or(config.namespace.as_deref())).map(str::to_string)
+    }
+
+    pub fn seed_display(&self, key: Option<Vec<u8>>) -> SeedDisplay {
+        SeedDisplay {
+            length: self.fingerprint_len as usize,
+            format: self.fingerprint_format,
+            key,
+        }
+    }
+
+    pub fn output_format(&self) -> OutputFormat {
+        if self.json {
+            OutputFormat::Json
+        } else {
+            self.format
+        }
     }
 
     pub fn get_year(&self) -> u32 {
         self.force_year.unwrap_or_else(|| Local::now().year() as u32)
     }
 
+    /// The year a commitment is for: `--force-year`, else the coming
+    /// New Year's draw (this year on January 1st).
+    pub fn commit_year(&self) -> u32 {
+        match self.force_year {
+            Some(year) => year,
+            None if self.is_january_first() => self.get_year(),
+            None => self.get_year() + 1,
+        }
+    }
+
     pub fn is_january_first(&self) -> bool {
         if let Some(ref date_str) = self.date {
             if let Some((_, rest)) = date_str.split_once('-') {
@@ -78,26 +293,25 @@ impl Args {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::hash::HashBits;
+    use crate::output::OmikujiResult;
+    use std::env;
 
     #[test]
     fn test_january_first_detection() {
         let args = Args {
             force_year: None,
             seed: Some("test".to_string()),
-            json: false,
-            short: false,
-            show_seed: false,
             date: Some("2026-01-01".to_string()),
+            ..Default::default()
         };
         assert!(args.is_january_first());
 
         let args = Args {
             force_year: None,
             seed: Some("test".to_string()),
-            json: false,
-            short: false,
-            show_seed: false,
             date: Some("2026-07-15".to_string()),
+            ..Default::default()
         };
         assert!(!args.is_january_first());
     }
@@ -107,10 +321,8 @@ mod tests {
         let args = Args {
             force_year: Some(2026),
             seed: Some("test".to_string()),
-            json: false,
-            short: false,
-            show_seed: false,
             date: Some("2026-07-15".to_string()),
+            ..Default::default()
         };
         assert!(args.can_execute().is_ok());
         assert_eq!(args.get_year(), 2026);
@@ -121,10 +333,8 @@ mod tests {
         let args = Args {
             force_year: None,
             seed: Some("test".to_string()),
-            json: false,
-            short: false,
-            show_seed: false,
             date: Some("2026-07-15".to_string()),
+            ..Default::default()
         };
         assert!(args.can_execute().is_err());
     }
@@ -134,12 +344,10 @@ mod tests {
         let args = Args {
             force_year: Some(2026),
             seed: Some("custom-seed".to_string()),
-            json: false,
-            short: false,
-            show_seed: false,
             date: None,
+            ..Default::default()
         };
-        assert_eq!(args.get_seed(), "custom-seed");
+        assert_eq!(args.get_seed().unwrap().0, "custom-seed");
     }
 
     #[test]
@@ -147,37 +355,119 @@ mod tests {
         let args = Args {
             force_year: None,
             seed: None,
-            json: false,
-            short: false,
-            show_seed: false,
             date: None,
+            ..Default::default()
         };
-        let seed = args.get_seed();
+        let (seed, _) = args.get_seed().unwrap();
         assert!(seed.contains('@'));
     }
 
+    #[test]
+    fn test_json_flag_selects_json_format() {
+        let args = Args {
+            json: true,
+            ..Default::default()
+        };
+        assert_eq!(args.output_format(), OutputFormat::Json);
+
+        let args = Args {
+            format: OutputFormat::Yaml,
+            ..Default::default()
+        };
+        assert_eq!(args.output_format(), OutputFormat::Yaml);
+    }
+
+    #[test]
+    fn test_get_seed_from_env_source() {
+        let args = Args {
+            seed_source: SeedSource::Env,
+            ..Default::default()
+        };
+        // The only test that touches this variable
+        env::set_var("HASH_OMIKUJI_SEED", "env-seed-42");
+        assert_eq!(args.get_seed().map(|(seed, _)| seed), Ok("env-seed-42".to_string()));
+        env::remove_var("HASH_OMIKUJI_SEED");
+        assert_eq!(args.get_seed().unwrap_err(), "HASH_OMIKUJI_SEED is not set");
+    }
+
+    #[test]
+    fn test_get_seed_normalized() {
+        let args = Args::parse_from(["hash-omikuji", "--seed", "  Alice ", "--normalize", "casefold,trim"]);
+        let (seed, steps) = args.get_seed().unwrap();
+        assert_eq!(seed, "alice");
+        assert_eq!(steps, vec![Normalization::Trim, Normalization::Casefold]);
+    }
+
+    #[test]
+    fn test_bare_invocation_is_draw() {
+        let args = Args::parse_from(["hash-omikuji", "--seed", "alice", "--json"]);
+        assert!(args.command.is_none());
+        let args = Args::parse_from(["hash-omikuji", "draw", "--seed", "alice", "--json"]);
+        assert!(matches!(args.command, Some(Command::Draw)));
+        assert!(args.json);
+    }
+
+    #[test]
+    fn test_global_options_after_subcommand() {
+        let args = Args::parse_from(["hash-omikuji", "verify", "fortune.json", "--seed", "alice", "--private"]);
+        assert!(matches!(args.command, Some(Command::Verify { .. })));
+        assert_eq!(args.seed.as_deref(), Some("alice"));
+        assert!(args.private);
+    }
+
+    #[test]
+    fn test_serve_rejects_zero_rate_limit() {
+        assert!(Args::try_parse_from(["hash-omikuji", "serve", "--rate-limit", "0"]).is_err());
+        let args = Args::parse_from(["hash-omikuji", "serve", "--rate-limit", "1"]);
+        assert!(matches!(args.command, Some(Command::Serve { rate_limit: 1, .. })));
+    }
+
+    #[test]
+    fn test_namespace_overrides_config() {
+        let config = Config {
+            namespace: Some("acme".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(Args::default().namespace(&config).as_deref(), Some("acme"));
+
+        let args = Args::parse_from(["hash-omikuji", "--namespace", "globex"]);
+        assert_eq!(args.namespace(&config).as_deref(), Some("globex"));
+
+        let args = Args::parse_from(["hash-omikuji", "--namespace", ""]);
+        assert_eq!(args.namespace(&config), None);
+    }
+
     #[test]
     fn test_get_year_with_force() {
         let args = Args {
             force_year: Some(2030),
             seed: None,
-            json: false,
-            short: false,
-            show_seed: false,
             date: None,
+            ..Default::default()
         };
         assert_eq!(args.get_year(), 2030);
     }
 
+    #[test]
+    fn test_force_year_range_round_trips() {
+        for year in ["1", "9999"] {
+            let args = Args::parse_from(["hash-omikuji", "--seed", "alice", "--force-year", year]);
+            let year = args.get_year();
+            let result = OmikujiResult::from_hash(&HashBits::from_seed(year, "alice"), year, "alice");
+            assert_eq!(OmikujiResult::from_json(&result.format_json()), Ok(result));
+        }
+        for year in ["0", "10000"] {
+            assert!(Args::try_parse_from(["hash-omikuji", "--seed", "alice", "--force-year", year]).is_err());
+        }
+    }
+
     #[test]
     fn test_get_year_default() {
         let args = Args {
             force_year: None,
             seed: None,
-            json: false,
-            short: false,
-            show_seed: false,
             date: None,
+            ..Default::default()
         };
         assert_eq!(args.get_year(), Local::now().year() as u32);
     }
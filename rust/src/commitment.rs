@@ -0,0 +1,276 @@
+use hash_omikuji::hash::{DeriveOptions, HashScheme};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+const NONCE_LEN: usize = 32;
+const COMMIT_TAG: &[u8] = b"hash-omikuji/commit/v2\0";
+const KEY_TAG: &[u8] = b"hash-omikuji/key\0";
+
+/// Everything the reveal has to match besides the nonce: the seed and
+/// every other input of the draw. The beacon's randomness is unknown at
+/// commit time, so only its round is bound; the `--private` key only
+/// through a fingerprint.
+#[derive(Debug, Clone, Copy)]
+pub struct DrawInputs<'a> {
+    pub seed: &'a str,
+    pub year: u32,
+    pub scheme: HashScheme,
+    pub namespace: Option<&'a str>,
+    pub beacon_round: Option<u64>,
+    pub key: Option<&'a [u8]>,
+}
+
+impl<'a> DrawInputs<'a> {
+    /// The inputs of drawing `seed` for `year` with `options`.
+    pub fn new(seed: &'a str, year: u32, options: &DeriveOptions<'a>) -> Self {
+        Self {
+            seed,
+            year,
+            scheme: options.scheme,
+            namespace: options.namespace,
+            beacon_round: options.beacon.map(|beacon| beacon.round),
+            key: options.key,
+        }
+    }
+
+    /// As in `DeriveOptions::effective_scheme`: the same draw whichever
+    /// scheme was requested.
+    fn effective_scheme(&self) -> HashScheme {
+        self.scheme.effective(self.namespace.is_some() || self.beacon_round.is_some())
+    }
+}
+
+/// A published commitment and the nonce its owner keeps until reveal.
+/// The public draw parameters are listed so others can check the reveal.
+#[derive(Debug, Serialize)]
+pub struct Commitment {
+    pub commitment: String,
+    pub nonce: String,
+    pub year: u32,
+    pub hash_scheme: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beacon_round: Option<u64>,
+    pub private: bool,
+}
+
+fn key_fingerprint(key: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(KEY_TAG);
+    hasher.update(key);
+    hasher.finalize().into()
+}
+
+/// Length-prefixed (u64 BE) bytes, or a 0 marker when absent.
+fn update_optional(hasher: &mut Sha256, field: Option<&[u8]>) {
+    match field {
+        Some(bytes) => {
+            hasher.update([1]);
+            hasher.update((bytes.len() as u64).to_be_bytes());
+            hasher.update(bytes);
+        }
+        None => hasher.update([0]),
+    }
+}
+
+/// SHA-256 over a domain tag, the fixed-length nonce, the year (u32 BE),
+/// the scheme number, then the seed, namespace, beacon round and key
+/// fingerprint, each length-prefixed (or marked absent), so no two
+/// different sets of inputs share an encoding.
+pub fn commitment_hash(inputs: &DrawInputs, nonce: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(COMMIT_TAG);
+    hasher.update(nonce);
+    hasher.update(inputs.year.to_be_bytes());
+    hasher.update([inputs.effective_scheme().number()]);
+    update_optional(&mut hasher, Some(inputs.seed.as_bytes()));
+    update_optional(&mut hasher, inputs.namespace.map(str::as_bytes));
+    update_optional(&mut hasher, inputs.beacon_round.map(u64::to_be_bytes).as_ref().map(|round| &round[..]));
+    update_optional(&mut hasher, inputs.key.map(key_fingerprint).as_ref().map(|fingerprint| &fingerprint[..]));
+    hex::encode(hasher.finalize())
+}
+
+/// Commits to `inputs` with a fresh random nonce.
+pub fn commit(inputs: &DrawInputs) -> Result<Commitment, String> {
+    let mut nonce = [0u8; NONCE_LEN];
+    getrandom::fill(&mut nonce).map_err(|e| format!("Failed to generate nonce: {}", e))?;
+    Ok(Commitment {
+        commitment: commitment_hash(inputs, &nonce),
+        nonce: hex::encode(nonce),
+        year: inputs.year,
+        hash_scheme: inputs.effective_scheme().number(),
+        namespace: inputs.namespace.map(str::to_string),
+        beacon_round: inputs.beacon_round,
+        private: inputs.key.is_some(),
+    })
+}
+
+/// Checks that `inputs` and `nonce` (hex) open `commitment` (hex).
+pub fn verify(inputs: &DrawInputs, nonce: &str, commitment: &str) -> Result<(), String> {
+    let nonce = hex::decode(nonce.trim()).map_err(|_| "Nonce must be hex".to_string())?;
+    if nonce.len() != NONCE_LEN {
+        return Err(format!("Nonce must be {} bytes ({} hex chars)", NONCE_LEN, NONCE_LEN * 2));
+    }
+    if commitment_hash(inputs, &nonce) != commitment.trim().to_ascii_lowercase() {
+        return Err(
+            "Commitment does not match this seed and nonce with this year, hash scheme, namespace, beacon round and key"
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
+impl Commitment {
+    pub fn format_text(&self) -> String {
+        let mut out = format!("Commitment : {}\nNonce      : {}\n\n", self.commitment, self.nonce);
+        out.push_str(&format!("Year       : {}\n", self.year));
+        out.push_str(&format!("Scheme     : {}\n", self.hash_scheme));
+        if let Some(ref namespace) = self.namespace {
+            out.push_str(&format!("Namespace  : {}\n", namespace));
+        }
+        if let Some(round) = self.beacon_round {
+            out.push_str(&format!("Beacon     : round {}\n", round));
+        }
+        if self.private {
+            out.push_str("Private    : yes (reveal with the same secret key)\n");
+        }
+        out.push_str("\nPublish the commitment now. Keep the nonce private until the reveal,\n");
+        out.push_str("which must use the same year and options.\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(seed: &str) -> DrawInputs<'_> {
+        DrawInputs {
+            seed,
+            year: 2027,
+            scheme: HashScheme::V1,
+            namespace: None,
+            beacon_round: None,
+            key: None,
+        }
+    }
+
+    #[test]
+    fn test_commit_then_verify() {
+        let c = commit(&inputs("alice")).unwrap();
+        assert_eq!(c.commitment.len(), 64);
+        assert_eq!(c.nonce.len(), NONCE_LEN * 2);
+        assert!(verify(&inputs("alice"), &c.nonce, &c.commitment).is_ok());
+    }
+
+    #[test]
+    fn test_verify_rejects_other_seed() {
+        let c = commit(&inputs("alice")).unwrap();
+        assert!(verify(&inputs("mallory"), &c.nonce, &c.commitment).is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_other_nonce() {
+        let c = commit(&inputs("alice")).unwrap();
+        let other = commit(&inputs("alice")).unwrap();
+        assert_ne!(c.nonce, other.nonce);
+        assert!(verify(&inputs("alice"), &other.nonce, &c.commitment).is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_malformed_nonce() {
+        let c = commit(&inputs("alice")).unwrap();
+        assert!(verify(&inputs("alice"), "zz", &c.commitment).is_err());
+        assert!(verify(&inputs("alice"), "abcd", &c.commitment).is_err());
+    }
+
+    #[test]
+    fn test_commitment_hash_depends_on_nonce() {
+        let hash = commitment_hash(&inputs("alice"), &[0u8; NONCE_LEN]);
+        assert_eq!(hash, commitment_hash(&inputs("alice"), &[0u8; NONCE_LEN]));
+        assert_ne!(hash, commitment_hash(&inputs("alice"), &[1u8; NONCE_LEN]));
+    }
+
+    #[test]
+    fn test_verify_rejects_any_other_draw_input() {
+        let key = [7u8; 32];
+        let committed = DrawInputs {
+            namespace: Some("acme"),
+            beacon_round: Some(42),
+            key: Some(&key),
+            ..inputs("alice")
+        };
+        let c = commit(&committed).unwrap();
+        assert!(verify(&committed, &c.nonce, &c.commitment).is_ok());
+
+        let other_key = [8u8; 32];
+        let changed = [
+            DrawInputs { year: 2028, ..committed },
+            DrawInputs { namespace: Some("globex"), ..committed },
+            DrawInputs { namespace: None, ..committed },
+            DrawInputs { beacon_round: Some(43), ..committed },
+            DrawInputs { beacon_round: None, ..committed },
+            DrawInputs { key: Some(&other_key), ..committed },
+            DrawInputs { key: None, ..committed },
+        ];
+        for inputs in changed {
+            assert!(verify(&inputs, &c.nonce, &c.commitment).is_err(), "Accepted {:?}", inputs);
+        }
+
+        let plain = commit(&inputs("alice")).unwrap();
+        let v2 = DrawInputs {
+            scheme: HashScheme::V2,
+            ..inputs("alice")
+        };
+        assert!(verify(&v2, &plain.nonce, &plain.commitment).is_err());
+    }
+
+    #[test]
+    fn test_scheme_is_bound_as_drawn() {
+        // A namespace or beacon always draws with scheme 2, whichever was requested
+        for committed in [
+            DrawInputs {
+                namespace: Some("acme"),
+                ..inputs("alice")
+            },
+            DrawInputs {
+                beacon_round: Some(42),
+                ..inputs("alice")
+            },
+        ] {
+            let c = commit(&committed).unwrap();
+            assert_eq!(c.hash_scheme, 2);
+            let v2 = DrawInputs {
+                scheme: HashScheme::V2,
+                ..committed
+            };
+            assert!(verify(&v2, &c.nonce, &c.commitment).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_absent_fields_cannot_pass_for_empty_ones() {
+        let nonce = [0u8; NONCE_LEN];
+        let without = inputs("alice");
+        let empty = DrawInputs {
+            namespace: Some(""),
+            ..without
+        };
+        assert_ne!(commitment_hash(&without, &nonce), commitment_hash(&empty, &nonce));
+    }
+
+    #[test]
+    fn test_commitment_lists_public_inputs_only() {
+        let key = [7u8; 32];
+        let c = commit(&DrawInputs {
+            namespace: Some("acme"),
+            key: Some(&key),
+            ..inputs("alice")
+        })
+        .unwrap();
+        let json = serde_json::to_string(&c).unwrap();
+        assert!(json.contains("\"namespace\":\"acme\"") && json.contains("\"private\":true"));
+        assert!(!json.contains("alice") && !json.contains("0707"));
+    }
+}
@@ -0,0 +1,124 @@
+use crate::cli::Args;
+use clap::CommandFactory;
+use clap_complete::Shell;
+use std::fs;
+use std::path::Path;
+
+const BIN_NAME: &str = "hash-omikuji";
+
+/// Completion script for `shell`, generated from `Args`.
+pub fn completions(shell: Shell) -> Vec<u8> {
+    let mut out = Vec::new();
+    clap_complete::generate(shell, &mut Args::command(), BIN_NAME, &mut out);
+    out
+}
+
+/// Man pages as (file name, roff): `hash-omikuji.1` first, then one page
+/// per subcommand such as `hash-omikuji-verify.1`.
+pub fn manpages() -> Result<Vec<(String, Vec<u8>)>, String> {
+    fn render(cmd: clap::Command, pages: &mut Vec<(String, Vec<u8>)>) -> Result<(), String> {
+        let man = clap_mangen::Man::new(cmd.clone());
+        let mut roff = Vec::new();
+        man.render(&mut roff).map_err(|e| e.to_string())?;
+        pages.push((man.get_filename(), roff));
+        for sub in cmd.get_subcommands().filter(|s| !s.is_hide_set()).cloned() {
+            render(sub, pages)?;
+        }
+        Ok(())
+    }
+
+    let mut cmd = Args::command().disable_help_subcommand(true);
+    cmd.build();
+    let mut pages = Vec::new();
+    render(cmd, &mut pages)?;
+    Ok(pages)
+}
+
+/// Writes every page of `manpages` into `dir`.
+pub fn write_manpages(dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    for (name, roff) in manpages()? {
+        let path = dir.join(name);
+        fs::write(&path, roff).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::ValueEnum;
+
+    /// Long flags of the command and all of its subcommands.
+    fn all_flags() -> Vec<String> {
+        fn collect(cmd: &clap::Command, flags: &mut Vec<String>) {
+            for arg in cmd.get_arguments() {
+                if let Some(long) = arg.get_long() {
+                    if !flags.iter().any(|f| f == long) {
+                        flags.push(long.to_string());
+                    }
+                }
+            }
+            for sub in cmd.get_subcommands() {
+                collect(sub, flags);
+            }
+        }
+        let mut cmd = Args::command();
+        cmd.build();
+        let mut flags = Vec::new();
+        collect(&cmd, &mut flags);
+        flags
+    }
+
+    #[test]
+    fn test_flags_include_subcommand_options() {
+        let flags = all_flags();
+        for flag in ["force-year", "seed", "json", "nonce", "team", "rate-limit", "jobs", "samples"] {
+            assert!(flags.iter().any(|f| f == flag), "missing --{}", flag);
+        }
+    }
+
+    #[test]
+    fn test_completions_contain_every_flag() {
+        for &shell in Shell::value_variants() {
+            let script = String::from_utf8(completions(shell)).unwrap();
+            for flag in all_flags() {
+                // fish declares long options as `-l name`
+                let expected = match shell {
+                    Shell::Fish => format!("-l {}", flag),
+                    _ => format!("--{}", flag),
+                };
+                assert!(script.contains(&expected), "{} completions lack --{}", shell, flag);
+            }
+        }
+    }
+
+    #[test]
+    fn test_completions_contain_subcommands() {
+        let script = String::from_utf8(completions(Shell::Bash)).unwrap();
+        for name in ["draw", "verify", "explain", "layout", "stats", "completions", "manpage", "batch"] {
+            assert!(script.contains(name), "bash completions lack {}", name);
+        }
+    }
+
+    #[test]
+    fn test_manpages_contain_every_flag() {
+        let pages = manpages().unwrap();
+        assert_eq!(pages[0].0, "hash-omikuji.1");
+        assert!(pages.iter().any(|(name, _)| name == "hash-omikuji-verify.1"));
+        let roff: String = pages.iter().map(|(_, page)| String::from_utf8_lossy(page)).collect();
+        for flag in all_flags() {
+            let expected = format!("\\-\\-{}", flag.replace('-', "\\-"));
+            assert!(roff.contains(&expected), "man pages lack --{}", flag);
+        }
+    }
+
+    #[test]
+    fn test_write_manpages() {
+        let dir = std::env::temp_dir().join(format!("hash-omikuji-man-{}", std::process::id()));
+        write_manpages(&dir).unwrap();
+        assert!(dir.join("hash-omikuji.1").exists());
+        assert!(dir.join("hash-omikuji-stats.1").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
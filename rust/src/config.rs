@@ -0,0 +1,72 @@
+use serde::Deserialize;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CONFIG_DIR_ENV: &str = "HASH_OMIKUJI_CONFIG_DIR";
+const CONFIG_FILE: &str = "config.toml";
+
+/// Settings read from `config.toml` in the config dir. Command line
+/// options take precedence over these.
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Default for `--namespace`
+    pub namespace: Option<String>,
+    /// Seeds shown on the `tui` team leaderboard
+    #[serde(default)]
+    pub team: Vec<String>,
+}
+
+/// `$HASH_OMIKUJI_CONFIG_DIR`, or `hash-omikuji` under the platform config dir.
+pub fn config_dir() -> Option<PathBuf> {
+    match env::var_os(CONFIG_DIR_ENV) {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => dirs::config_dir().map(|dir| dir.join("hash-omikuji")),
+    }
+}
+
+impl Config {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        toml::from_str(text).map_err(|e| e.to_string())
+    }
+
+    /// Reads `config.toml` from `dir`; a missing file means all defaults.
+    pub fn load(dir: &Path) -> Result<Self, String> {
+        let path = dir.join(CONFIG_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse(&text).map_err(|e| format!("Invalid {}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_namespace() {
+        let config = Config::parse("namespace = \"acme\"\n").unwrap();
+        assert_eq!(config.namespace.as_deref(), Some("acme"));
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_parse_team() {
+        let config = Config::parse("team = [\"alice\", \"bob\"]\n").unwrap();
+        assert_eq!(config.team, vec!["alice", "bob"]);
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_settings() {
+        assert!(Config::parse("namespcae = \"acme\"\n").is_err());
+    }
+
+    #[test]
+    fn test_missing_file_is_default() {
+        let dir = env::temp_dir().join(format!("hash-omikuji-test-no-config-{}", std::process::id()));
+        assert_eq!(Config::load(&dir).unwrap(), Config::default());
+    }
+}
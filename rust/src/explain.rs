@@ -0,0 +1,188 @@
+use crate::hash::{BitField, DeriveOptions, HashBits, HashScheme, LAYOUT};
+use crate::luck::{LuckScore, LuckType};
+
+const HIGHLIGHT_ON: &str = "\x1b[7m";
+const HIGHLIGHT_OFF: &str = "\x1b[0m";
+
+/// Printable ASCII as is, everything else as `\xNN`.
+fn escape_bytes(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|&b| match b {
+            b'"' | b'\\' => format!("\\{}", b as char),
+            0x20..=0x7E => (b as char).to_string(),
+            _ => format!("\\x{:02x}", b),
+        })
+        .collect()
+}
+
+/// How the fingerprint is computed: the exact hash input, the algorithm and
+/// the resulting digest. The secret key of `--private` is never shown.
+pub fn derivation(year: u32, seed: &str, options: &DeriveOptions) -> String {
+    let input = HashBits::input(year, seed, options);
+    let hash = HashBits::derive(year, seed, options);
+    let encoding = match options.effective_scheme() {
+        HashScheme::V1 => "1 (\"{year}-{seed}-{salt}\")",
+        HashScheme::V2 => "2 (tag, u32 BE year, u64 BE length-prefixed fields)",
+    };
+    let algorithm = if options.key.is_some() { "HMAC-SHA256 (secret key)" } else { "SHA-256" };
+
+    let mut out = String::new();
+    out.push_str(&format!("Year        : {}\n", year));
+    out.push_str(&format!("Seed        : \"{}\"\n", escape_bytes(seed.as_bytes())));
+    out.push_str(&format!("Scheme      : {}\n", encoding));
+    if let Some(namespace) = options.namespace {
+        out.push_str(&format!("Namespace   : \"{}\"\n", escape_bytes(namespace.as_bytes())));
+    }
+    if let Some(beacon) = options.beacon {
+        out.push_str(&format!("Beacon      : round {}\n", beacon.round));
+    }
+    out.push_str(&format!("Input       : \"{}\" ({} bytes)\n", escape_bytes(&input), input.len()));
+    out.push_str(&format!("Algorithm   : {}\n", algorithm));
+    out.push_str(&format!("Fingerprint : {}\n", hash.hex_string()));
+    out
+}
+
+/// The 256-bit layout as a table.
+pub fn layout() -> String {
+    let mut out = format!("{:<9} {:>4}  {:<18} {}\n", "Bits", "Size", "Field", "Range");
+    for field in LAYOUT {
+        let bits = format!("{}-{}", field.start, field.end());
+        out.push_str(&format!("{:<9} {:>4}  {:<18} {}\n", bits, field.bits, field.name, field.range));
+    }
+    out
+}
+
+/// Displayed value of each `LAYOUT` field, in order.
+fn field_values(hash: &HashBits) -> [String; 18] {
+    [
+        hash.lucky_number().to_string(),
+        format!("0x{:02X}", hash.lucky_hex()),
+        format!("{:016b}", hash.lucky_bits()),
+        hash.lucky_day().to_string(),
+        hash.lucky_hour().to_string(),
+        hash.lucky_minute().to_string(),
+        hash.lucky_power_of_2().to_string(),
+        format!("'{}'", hash.lucky_ascii()),
+        hash.lucky_logic_gate().to_string(),
+        String::new(),
+        format!("0x{:03X}", hash.entropy_check()),
+        hash.lucky_emoji().to_string(),
+        hash.lucky_direction().to_string(),
+        hash.lucky_element().to_string(),
+        format!("{}%", hash.lucky_percent()),
+        format!("{}°", hash.lucky_latitude()),
+        format!("{}°", hash.lucky_longitude()),
+        "-".to_string(),
+    ]
+}
+
+/// The fingerprint with the nibbles holding bits `start..=end` marked:
+/// reverse video when `color`, else [brackets].
+fn highlight_hex(hex: &str, start: usize, end: usize, color: bool) -> String {
+    let (from, to) = (start / 4, end / 4 + 1);
+    let (on, off) = if color { (HIGHLIGHT_ON, HIGHLIGHT_OFF) } else { ("[", "]") };
+    format!("{}{}{}{}{}", &hex[..from], on, &hex[from..to], off, &hex[to..])
+}
+
+/// The field's bits, in groups of 4 from its first bit.
+fn binary(hash: &HashBits, start: usize, bits: usize) -> String {
+    let digits: Vec<String> = (start..start + bits).map(|bit| hash.get_bits(bit, 1).to_string()).collect();
+    digits.chunks(4).map(|group| group.concat()).collect::<Vec<_>>().join(" ")
+}
+
+fn explain_field(out: &mut String, hash: &HashBits, field: &BitField, mapping: &str, value: &str, color: bool) {
+    let raw = hash.get_bits(field.start, field.bits);
+    let bits = format!("{}-{}", field.start, field.end());
+    out.push_str(&format!("{:<18} bits {:<8} raw {:<6} {} = {}\n", field.name, bits, raw, mapping, value));
+    out.push_str(&format!("  hex {}\n", highlight_hex(&hash.hex_string(), field.start, field.end(), color)));
+    out.push_str(&format!("  bin {}\n", binary(hash, field.start, field.bits)));
+}
+
+/// For every field: its bits, the raw integer `HashBits::get_bits`
+/// extracts, the mapping applied and the final value, with the field's
+/// slice of the fingerprint highlighted. Luck Scores are shown per type.
+pub fn fields(hash: &HashBits, color: bool) -> String {
+    let mut out = String::new();
+    for (field, value) in LAYOUT.iter().zip(field_values(hash)) {
+        if field.bits > 64 {
+            let scores = hash.luck_scores();
+            for (i, luck_type) in LuckType::ALL.iter().enumerate() {
+                let score = LuckScore::new(*luck_type, scores[i]);
+                let sub = BitField {
+                    name: luck_type.name(),
+                    start: field.start + i * 8,
+                    bits: 8,
+                    ..*field
+                };
+                let value = format!("{} ({})", score.score, score.rank.as_str());
+                explain_field(&mut out, hash, &sub, "value * 100 / 255", &value, color);
+            }
+        } else {
+            explain_field(&mut out, hash, field, field.mapping, &value, color);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_bytes() {
+        assert_eq!(escape_bytes(b"a\"b\\c\0\xff"), "a\\\"b\\\\c\\x00\\xff");
+    }
+
+    #[test]
+    fn test_derivation_shows_input_and_fingerprint() {
+        let text = derivation(2026, "alice", &DeriveOptions::default());
+        assert!(text.contains("Input       : \"2026-alice-sha-omikuji-2026\" (27 bytes)"));
+        assert!(text.contains(&HashBits::from_seed(2026, "alice").hex_string()));
+        assert!(text.contains("SHA-256"));
+    }
+
+    #[test]
+    fn test_derivation_hides_key() {
+        let key = [0xAB; 32];
+        let options = DeriveOptions {
+            key: Some(&key),
+            ..Default::default()
+        };
+        let text = derivation(2026, "alice", &options);
+        assert!(text.contains("HMAC-SHA256"));
+        assert!(!text.contains("abab"));
+    }
+
+    #[test]
+    fn test_layout_lists_every_field() {
+        let text = layout();
+        assert_eq!(text.lines().count(), 1 + LAYOUT.len());
+        assert!(text.contains("65-192     128  Luck Scores"));
+    }
+
+    #[test]
+    fn test_highlight_hex() {
+        assert_eq!(highlight_hex("a5bf2534", 0, 7, false), "[a5]bf2534");
+        assert_eq!(highlight_hex("a5bf2534", 8, 11, true), "a5\x1b[7mb\x1b[0mf2534");
+    }
+
+    #[test]
+    fn test_fields_match_hash_bits() {
+        let hash = HashBits::from_seed(2026, "test-user");
+        let text = fields(&hash, false);
+        // 17 plain fields plus 16 luck types, three lines each
+        assert_eq!(text.lines().count(), (17 + 16) * 3);
+        let hour = text.lines().find(|line| line.starts_with("Lucky Hour")).unwrap();
+        let raw = hash.get_bits(41, 5);
+        assert_eq!(hour, format!("Lucky Hour         bits 41-45    raw {:<6} value % 24 = {}", raw, raw % 24));
+        let day = text.lines().skip_while(|line| !line.starts_with("Lucky Day")).nth(2).unwrap();
+        assert_eq!(day, format!("  bin {}", binary(&hash, 32, 9)));
+        assert_eq!(binary(&hash, 32, 9).replace(' ', "").len(), 9);
+    }
+
+    #[test]
+    fn test_snapshot_fields() {
+        insta::assert_snapshot!(fields(&HashBits::from_seed(2026, "test-user"), false));
+    }
+}
@@ -0,0 +1,183 @@
+use crate::beacon::Beacon;
+use crate::format::{self, OutputFormat};
+use crate::hash::{self, DeriveOptions, HashBits, HashScheme};
+use crate::normalize::{normalize, Normalization};
+use crate::output::OmikujiResult;
+use crate::privacy::SeedDisplay;
+use crate::schema::SchemaVersion;
+use clap::ValueEnum;
+use serde::Deserialize;
+use serde_json::json;
+use std::ffi::{c_char, CStr, CString};
+
+/// `options_json` of `hash_omikuji_draw` (declared in include/hash_omikuji.h).
+/// Every field is optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct DrawOptions {
+    /// Hash input scheme, 1 or 2
+    scheme: Option<u8>,
+    namespace: Option<String>,
+    beacon: Option<Beacon>,
+    /// Normalization names, e.g. ["trim", "casefold"]
+    normalize: Vec<String>,
+    /// Secret key as hex, as in `--private`
+    key: Option<String>,
+    /// JSON layout version, 1 or 2
+    schema_version: Option<u8>,
+}
+
+fn draw_json(seed: &str, year: u32, options_json: Option<&str>) -> Result<String, String> {
+    let options: DrawOptions = match options_json {
+        Some(json) => serde_json::from_str(json).map_err(|e| format!("Invalid options: {}", e))?,
+        None => DrawOptions::default(),
+    };
+    if !(1..=9999).contains(&year) {
+        return Err(format!("year must be 1-9999: {}", year));
+    }
+    let scheme = options.scheme.map_or(Ok(HashScheme::V1), HashScheme::from_number)?;
+    if let Some(ref beacon) = options.beacon {
+        beacon.validate()?;
+    }
+    let key = options
+        .key
+        .as_deref()
+        .map(|key| hex::decode(key).map_err(|_| "key must be hex".to_string()))
+        .transpose()?;
+    let steps = options
+        .normalize
+        .iter()
+        .map(|name| Normalization::from_str(name, false).map_err(|_| format!("Unknown normalization: {}", name)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let version = match options.schema_version {
+        None | Some(1) => SchemaVersion::V1,
+        Some(2) => SchemaVersion::V2,
+        Some(other) => return Err(format!("Unknown schema version: {}", other)),
+    };
+
+    let (seed, steps) = normalize(seed, &steps);
+    let derive = DeriveOptions {
+        scheme,
+        namespace: hash::non_empty_namespace(options.namespace.as_deref()),
+        beacon: options.beacon.as_ref(),
+        key: key.as_deref(),
+    };
+    let hash = HashBits::derive(year, &seed, &derive);
+    let display = SeedDisplay {
+        key,
+        ..Default::default()
+    };
+    let result = OmikujiResult::from_hash(&hash, year, &seed)
+        .with_seed_display(&seed, &display)
+        .with_seed_normalization(&steps);
+    let bytes = format::render(&result, OutputFormat::JsonCompact, version)?;
+    Ok(String::from_utf8(bytes).expect("JSON is UTF-8").trim_end().to_string())
+}
+
+/// Reads a nullable C string; NULL becomes `None`.
+///
+/// # Safety
+/// `ptr` must be NULL or point to a NUL-terminated string.
+unsafe fn optional_str<'a>(ptr: *const c_char, name: &str) -> Result<Option<&'a str>, String> {
+    if ptr.is_null() {
+        return Ok(None);
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map(Some)
+        .map_err(|_| format!("{} is not valid UTF-8", name))
+}
+
+/// Draws the fortune for `seed` and `year` and returns it as a JSON
+/// string, or `{"error": "..."}` when the arguments are invalid.
+/// `options_json` may be NULL. Free the result with `hash_omikuji_free`.
+///
+/// # Safety
+/// `seed` and `options_json` must each be NULL or point to a
+/// NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn hash_omikuji_draw(seed: *const c_char, year: u32, options_json: *const c_char) -> *mut c_char {
+    let json = optional_str(seed, "seed")
+        .and_then(|seed| seed.ok_or_else(|| "seed must not be NULL".to_string()))
+        .and_then(|seed| Ok((seed, optional_str(options_json, "options")?)))
+        .and_then(|(seed, options)| draw_json(seed, year, options))
+        .unwrap_or_else(|msg| json!({ "error": msg }).to_string());
+    CString::new(json).expect("JSON has no NUL bytes").into_raw()
+}
+
+/// Frees a string returned by `hash_omikuji_draw`. NULL is ignored.
+///
+/// # Safety
+/// `json` must be NULL or a pointer returned by `hash_omikuji_draw` that
+/// has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn hash_omikuji_free(json: *mut c_char) {
+    if !json.is_null() {
+        drop(CString::from_raw(json));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr;
+
+    fn call(seed: Option<&str>, year: u32, options: Option<&str>) -> serde_json::Value {
+        let seed = seed.map(|s| CString::new(s).unwrap());
+        let options = options.map(|s| CString::new(s).unwrap());
+        unsafe {
+            let ptr = hash_omikuji_draw(
+                seed.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
+                year,
+                options.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
+            );
+            let json = CStr::from_ptr(ptr).to_str().unwrap().to_string();
+            hash_omikuji_free(ptr);
+            serde_json::from_str(&json).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_draw_matches_cli_json() {
+        let value = call(Some("test-user"), 2026, None);
+        let expected = OmikujiResult::from_hash(&HashBits::from_seed(2026, "test-user"), 2026, "test-user");
+        assert_eq!(value, serde_json::to_value(&expected).unwrap());
+    }
+
+    #[test]
+    fn test_draw_with_options() {
+        let options = r#"{"scheme": 2, "namespace": "acme", "normalize": ["casefold"], "schema_version": 2}"#;
+        let value = call(Some("ALICE"), 2026, Some(options));
+        assert_eq!(value["schema_version"], 2);
+        assert_eq!(value["namespace"], "acme");
+        assert_eq!(value["hash_scheme"], 2);
+        assert_eq!(value, call(Some("alice"), 2026, Some(options)));
+    }
+
+    #[test]
+    fn test_empty_namespace_is_no_namespace() {
+        let empty = call(Some("alice"), 2026, Some(r#"{"namespace": ""}"#));
+        assert_eq!(empty, call(Some("alice"), 2026, None));
+        assert!(empty.get("hash_scheme").is_none());
+    }
+
+    #[test]
+    fn test_private_key_changes_fortune() {
+        let plain = call(Some("alice"), 2026, None);
+        let keyed = call(Some("alice"), 2026, Some(&format!(r#"{{"key": "{}"}}"#, "07".repeat(32))));
+        assert_ne!(plain["fingerprint"], keyed["fingerprint"]);
+    }
+
+    #[test]
+    fn test_errors_are_json() {
+        assert!(call(None, 2026, None)["error"].as_str().unwrap().contains("NULL"));
+        assert!(call(Some("alice"), 2026, Some("{\"colour\": 1}"))["error"].is_string());
+        assert!(call(Some("alice"), 2026, Some("{\"scheme\": 9}"))["error"].is_string());
+        assert!(call(Some("alice"), 0, None)["error"].is_string());
+    }
+
+    #[test]
+    fn test_free_ignores_null() {
+        unsafe { hash_omikuji_free(ptr::null_mut()) };
+    }
+}
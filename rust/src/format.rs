@@ -0,0 +1,324 @@
+use crate::chart::ScoreStyle;
+use crate::chat;
+use crate::luck::LuckType;
+use crate::output::OmikujiResult;
+use crate::schema::{OmikujiResultV2, SchemaVersion};
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable fortune slip
+    #[default]
+    Text,
+    /// Pretty-printed JSON
+    Json,
+    /// Single-line JSON
+    JsonCompact,
+    Yaml,
+    Toml,
+    /// One row per fortune, one column per luck type
+    Csv,
+    /// Newline-delimited JSON
+    Ndjson,
+    /// Binary MessagePack
+    Msgpack,
+    /// Slack message (Block Kit) with the lucky color as sidebar
+    SlackBlocks,
+    /// Discord webhook message with one embed
+    DiscordEmbed,
+}
+
+/// CSV column names: the scalar fields of `OmikujiResult`, with `beacon`
+/// split into `beacon_round`/`beacon_randomness` and `luck_scores`
+/// flattened into one `luck_<type>` column per `LuckType`. Optional fields
+/// are empty cells when unset; `seed_normalization` is comma-joined.
+pub fn csv_header() -> Vec<String> {
+    let mut header: Vec<String> = [
+        "year",
+        "seed",
+        "namespace",
+        "beacon_round",
+        "beacon_randomness",
+        "seed_normalization",
+        "hash_scheme",
+        "lucky_number",
+        "lucky_hex",
+        "lucky_color",
+        "lucky_bits",
+        "lucky_day",
+        "lucky_day_number",
+        "lucky_time",
+        "lucky_power_of_2",
+        "lucky_ascii",
+        "lucky_logic_gate",
+        "lucky_emoji",
+        "lucky_direction",
+        "lucky_element",
+        "lucky_percent",
+        "lucky_latitude",
+        "lucky_longitude",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    header.extend(LuckType::ALL.iter().map(|t| format!("luck_{:?}", t).to_lowercase()));
+    header.push("entropy_check".to_string());
+    header.push("fingerprint".to_string());
+    header
+}
+
+pub fn csv_record(result: &OmikujiResult) -> Vec<String> {
+    let mut record = vec![
+        result.year.to_string(),
+        result.seed.clone(),
+        result.namespace.clone().unwrap_or_default(),
+        result.beacon.as_ref().map(|b| b.round.to_string()).unwrap_or_default(),
+        result.beacon.as_ref().map(|b| b.randomness.clone()).unwrap_or_default(),
+        result.seed_normalization.join(","),
+        result.hash_scheme.map(|n| n.to_string()).unwrap_or_default(),
+        result.lucky_number.to_string(),
+        result.lucky_hex.clone(),
+        result.lucky_color.clone(),
+        result.lucky_bits.clone(),
+        result.lucky_day.clone(),
+        result.lucky_day_number.to_string(),
+        result.lucky_time.clone(),
+        result.lucky_power_of_2.to_string(),
+        result.lucky_ascii.to_string(),
+        result.lucky_logic_gate.clone(),
+        result.lucky_emoji.clone(),
+        result.lucky_direction.clone(),
+        result.lucky_element.clone(),
+        result.lucky_percent.to_string(),
+        result.lucky_latitude.to_string(),
+        result.lucky_longitude.to_string(),
+    ];
+    for luck_type in LuckType::ALL {
+        let score = result.luck_scores.iter().find(|s| s.luck_type == luck_type);
+        record.push(score.map(|s| s.score.to_string()).unwrap_or_default());
+    }
+    record.push(result.entropy_check.clone());
+    record.push(result.fingerprint.clone());
+    record
+}
+
+/// Writes `results` as CSV with a single header row.
+pub fn format_csv(results: &[&OmikujiResult]) -> Result<Vec<u8>, String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(csv_header()).map_err(|e| e.to_string())?;
+    for result in results {
+        writer.write_record(csv_record(result)).map_err(|e| e.to_string())?;
+    }
+    writer.into_inner().map_err(|e| e.to_string())
+}
+
+/// The formats serde writes directly.
+#[derive(Debug, Clone, Copy)]
+enum SerdeFormat {
+    Json,
+    JsonLine,
+    Yaml,
+    Toml,
+    Msgpack,
+}
+
+/// How an `OutputFormat` is produced: by serde, or by a fixed layout.
+enum Layout {
+    Text,
+    Csv,
+    SlackBlocks,
+    DiscordEmbed,
+    Serde(SerdeFormat),
+}
+
+impl OutputFormat {
+    fn layout(self) -> Layout {
+        match self {
+            OutputFormat::Text => Layout::Text,
+            OutputFormat::Csv => Layout::Csv,
+            OutputFormat::SlackBlocks => Layout::SlackBlocks,
+            OutputFormat::DiscordEmbed => Layout::DiscordEmbed,
+            OutputFormat::Json => Layout::Serde(SerdeFormat::Json),
+            OutputFormat::JsonCompact | OutputFormat::Ndjson => Layout::Serde(SerdeFormat::JsonLine),
+            OutputFormat::Yaml => Layout::Serde(SerdeFormat::Yaml),
+            OutputFormat::Toml => Layout::Serde(SerdeFormat::Toml),
+            OutputFormat::Msgpack => Layout::Serde(SerdeFormat::Msgpack),
+        }
+    }
+}
+
+fn serialize<T: Serialize>(value: &T, format: SerdeFormat) -> Result<Vec<u8>, String> {
+    let bytes = match format {
+        SerdeFormat::Json => {
+            let mut json = serde_json::to_vec_pretty(value).map_err(|e| e.to_string())?;
+            json.push(b'\n');
+            json
+        }
+        SerdeFormat::JsonLine => {
+            let mut line = serde_json::to_vec(value).map_err(|e| e.to_string())?;
+            line.push(b'\n');
+            line
+        }
+        SerdeFormat::Yaml => serde_yaml::to_string(value).map_err(|e| e.to_string())?.into_bytes(),
+        SerdeFormat::Toml => toml::to_string(value).map_err(|e| e.to_string())?.into_bytes(),
+        SerdeFormat::Msgpack => rmp_serde::to_vec_named(value).map_err(|e| e.to_string())?,
+    };
+    Ok(bytes)
+}
+
+fn chat_payload(
+    payload: serde_json::Value,
+    validate: fn(&serde_json::Value) -> Result<(), String>,
+) -> Result<Vec<u8>, String> {
+    validate(&payload)?;
+    serialize(&payload, SerdeFormat::Json)
+}
+
+/// Serializes any other report (stats, verification...) in a serde format;
+/// text, CSV and the chat payloads are specific to fortunes.
+pub fn render_value<T: Serialize>(value: &T, format: OutputFormat) -> Result<Vec<u8>, String> {
+    match format.layout() {
+        Layout::Serde(serde_format) => serialize(value, serde_format),
+        _ => Err(format!(
+            "--format {} is not supported here",
+            format.to_possible_value().expect("no skipped variants").get_name()
+        )),
+    }
+}
+
+/// Serializes a result in any machine-readable format.
+/// `OutputFormat::Text` falls back to the default text layout; CSV and the
+/// chat payloads have fixed layouts regardless of `version`.
+pub fn render(result: &OmikujiResult, format: OutputFormat, version: SchemaVersion) -> Result<Vec<u8>, String> {
+    match (format.layout(), version) {
+        (Layout::Text, _) => Ok(result.format_text(false, false, ScoreStyle::Plain, false).into_bytes()),
+        (Layout::Csv, _) => format_csv(&[result]),
+        (Layout::SlackBlocks, _) => chat_payload(chat::slack_blocks(result), chat::validate_slack),
+        (Layout::DiscordEmbed, _) => chat_payload(chat::discord_embed(result), chat::validate_discord),
+        (Layout::Serde(SerdeFormat::Json), SchemaVersion::V1) => Ok(format!("{}\n", result.format_json()).into_bytes()),
+        (Layout::Serde(serde_format), SchemaVersion::V1) => serialize(result, serde_format),
+        (Layout::Serde(serde_format), SchemaVersion::V2) => serialize(&OmikujiResultV2::try_from(result)?, serde_format),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::beacon::Beacon;
+    use crate::hash::HashBits;
+
+    fn create_test_result() -> OmikujiResult {
+        let hash = HashBits::from_seed(2026, "test-user");
+        OmikujiResult::from_hash(&hash, 2026, "test-user")
+    }
+
+    fn render_string(format: OutputFormat) -> String {
+        String::from_utf8(render(&create_test_result(), format, SchemaVersion::V1).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_json_compact_is_single_line() {
+        let json = render_string(OutputFormat::JsonCompact);
+        assert_eq!(json.lines().count(), 1);
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["year"], 2026);
+    }
+
+    #[test]
+    fn test_yaml_parses() {
+        let yaml = render_string(OutputFormat::Yaml);
+        let parsed: serde_yaml::Value = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed["lucky_number"], serde_yaml::Value::from(95));
+    }
+
+    #[test]
+    fn test_toml_parses() {
+        let text = render_string(OutputFormat::Toml);
+        let parsed: toml::Table = text.parse().unwrap();
+        assert_eq!(parsed["year"].as_integer(), Some(2026));
+        assert_eq!(parsed["luck_scores"].as_array().unwrap().len(), 16);
+    }
+
+    #[test]
+    fn test_csv_has_column_per_luck_type() {
+        let csv_text = render_string(OutputFormat::Csv);
+        let mut reader = csv::Reader::from_reader(csv_text.as_bytes());
+        let headers = reader.headers().unwrap().clone();
+        for luck_type in LuckType::ALL {
+            let column = format!("luck_{:?}", luck_type).to_lowercase();
+            assert!(headers.iter().any(|h| h == column), "Missing column: {}", column);
+        }
+        let rows: Vec<_> = reader.records().collect();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].as_ref().unwrap().len(), headers.len());
+    }
+
+    #[test]
+    fn test_csv_header_matches_record() {
+        assert_eq!(csv_header().len(), csv_record(&create_test_result()).len());
+    }
+
+    #[test]
+    fn test_csv_header_covers_json_keys() {
+        let mut result = create_test_result();
+        result.namespace = Some("acme".to_string());
+        result.beacon = Some(Beacon {
+            round: 42,
+            randomness: "ab".repeat(32),
+        });
+        result.seed_normalization = vec!["nfc".to_string(), "trim".to_string()];
+        result.hash_scheme = Some(2);
+        let json = serde_json::to_value(&result).unwrap();
+        let mut keys: Vec<String> = Vec::new();
+        for key in json.as_object().unwrap().keys() {
+            match key.as_str() {
+                "beacon" => keys.extend(["beacon_round".to_string(), "beacon_randomness".to_string()]),
+                "luck_scores" => keys.extend(LuckType::ALL.iter().map(|t| format!("luck_{:?}", t).to_lowercase())),
+                _ => keys.push(key.clone()),
+            }
+        }
+        let mut header = csv_header();
+        keys.sort();
+        header.sort();
+        assert_eq!(header, keys);
+
+        let record = csv_record(&result);
+        let cell = |name: &str| &record[csv_header().iter().position(|h| h == name).unwrap()];
+        assert_eq!(cell("namespace"), "acme");
+        assert_eq!(cell("beacon_round"), "42");
+        assert_eq!(cell("seed_normalization"), "nfc,trim");
+        assert_eq!(cell("hash_scheme"), "2");
+    }
+
+    #[test]
+    fn test_render_value_rejects_fixed_layouts() {
+        let value = serde_json::json!({ "samples": 3 });
+        assert_eq!(render_value(&value, OutputFormat::JsonCompact).unwrap(), b"{\"samples\":3}\n");
+        for format in [OutputFormat::Text, OutputFormat::Csv, OutputFormat::SlackBlocks, OutputFormat::DiscordEmbed] {
+            assert!(render_value(&value, format).is_err());
+        }
+    }
+
+    #[test]
+    fn test_msgpack_round_trip() {
+        let bytes = render(&create_test_result(), OutputFormat::Msgpack, SchemaVersion::V1).unwrap();
+        let value: serde_json::Value = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(value["fingerprint"], create_test_result().fingerprint);
+    }
+
+    #[test]
+    fn test_v2_applies_to_all_serde_formats() {
+        let result = create_test_result();
+        let yaml = render(&result, OutputFormat::Yaml, SchemaVersion::V2).unwrap();
+        assert!(String::from_utf8(yaml).unwrap().contains("schema_version: 2"));
+        let json = render(&result, OutputFormat::JsonCompact, SchemaVersion::V2).unwrap();
+        let parsed: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(parsed["lucky_time"]["minute"], 12);
+    }
+
+    #[test]
+    fn test_snapshot_csv_output() {
+        insta::assert_snapshot!(render_string(OutputFormat::Csv));
+    }
+}
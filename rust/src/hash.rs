@@ -1,27 +1,234 @@
+use crate::beacon::Beacon;
+use clap::ValueEnum;
+use hmac::{Hmac, Mac};
 use sha2::{Sha256, Digest};
 
 const SALT: &str = "sha-omikuji-2026";
+const SCHEME_V2_TAG: &[u8] = b"hash-omikuji/v2\0";
+
+/// How (year, seed, salt) are encoded into the SHA-256 input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum HashScheme {
+    /// "{year}-{seed}-{salt}" (original; dashes make fields ambiguous)
+    #[default]
+    #[value(name = "1")]
+    V1,
+    /// Domain tag, big-endian year, then length-prefixed seed and salt
+    #[value(name = "2")]
+    V2,
+}
+
+impl HashScheme {
+    pub fn number(&self) -> u8 {
+        match self {
+            HashScheme::V1 => 1,
+            HashScheme::V2 => 2,
+        }
+    }
+
+    /// The scheme actually used: always 2 when `extra_fields` (a namespace
+    /// or beacon) are mixed in, since scheme 1's dashes can't tell them
+    /// from the seed or from each other.
+    pub fn effective(self, extra_fields: bool) -> Self {
+        if extra_fields {
+            HashScheme::V2
+        } else {
+            self
+        }
+    }
+
+    pub fn from_number(number: u8) -> Result<Self, String> {
+        match number {
+            1 => Ok(HashScheme::V1),
+            2 => Ok(HashScheme::V2),
+            _ => Err(format!("Unknown hash scheme: {}", number)),
+        }
+    }
+}
+
+/// Everything besides the year and seed that goes into the derivation.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DeriveOptions<'a> {
+    /// Requested scheme; see `effective_scheme`
+    pub scheme: HashScheme,
+    /// Organization namespace, appended after the salt (forces scheme 2)
+    pub namespace: Option<&'a str>,
+    /// Public randomness round, appended after the namespace (forces scheme 2)
+    pub beacon: Option<&'a Beacon>,
+    /// Secret key for HMAC-SHA256 (see `--private`)
+    pub key: Option<&'a [u8]>,
+}
+
+impl DeriveOptions<'_> {
+    pub fn effective_scheme(&self) -> HashScheme {
+        self.scheme.effective(self.namespace.is_some() || self.beacon.is_some())
+    }
+}
+
+/// A namespace as given by the user. An empty name means no namespace,
+/// so every frontend draws the same fortune for "" as for no namespace.
+pub fn non_empty_namespace(name: Option<&str>) -> Option<&str> {
+    name.filter(|name| !name.is_empty())
+}
 
 pub struct HashBits {
     bytes: [u8; 32],
+    scheme: HashScheme,
+    namespace: Option<String>,
+    beacon: Option<Beacon>,
+}
+
+/// V1 joins the fields with dashes: "{year}-{seed}-{salt}" (only ever used
+/// for those two fields; see `HashScheme::effective`).
+/// V2 layout: "hash-omikuji/v2\0" | year (u32 BE) | for each field:
+/// length (u64 BE) | bytes. Each field's extent is fixed by its length
+/// prefix, so distinct tuples can never encode to the same input.
+fn encode_input(scheme: HashScheme, year: u32, fields: &[&str]) -> Vec<u8> {
+    match scheme {
+        HashScheme::V1 => {
+            let mut input = year.to_string();
+            for field in fields {
+                input.push('-');
+                input.push_str(field);
+            }
+            input.into_bytes()
+        }
+        HashScheme::V2 => {
+            let mut input = SCHEME_V2_TAG.to_vec();
+            input.extend_from_slice(&year.to_be_bytes());
+            for field in fields.iter().map(|f| f.as_bytes()) {
+                input.extend_from_slice(&(field.len() as u64).to_be_bytes());
+                input.extend_from_slice(field);
+            }
+            input
+        }
+    }
+}
+
+/// A field of the 256-bit layout (see README "Bit Layout").
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitField {
+    pub name: &'static str,
+    pub start: usize,
+    pub bits: usize,
+    pub range: &'static str,
+    /// How the extracted value becomes the displayed one (see `--explain`)
+    pub mapping: &'static str,
+}
+
+impl BitField {
+    /// Last bit of the field (inclusive).
+    pub fn end(&self) -> usize {
+        self.start + self.bits - 1
+    }
 }
 
+pub const LAYOUT: [BitField; 18] = [
+    BitField { name: "Lucky Number", start: 0, bits: 8, range: "0-255", mapping: "value" },
+    BitField { name: "Lucky Hex", start: 8, bits: 8, range: "0x00-0xFF", mapping: "value as hex" },
+    BitField { name: "Lucky Bits", start: 16, bits: 16, range: "display pattern", mapping: "value in groups of 4 bits" },
+    BitField { name: "Lucky Day", start: 32, bits: 9, range: "1-365", mapping: "value % 365 + 1" },
+    BitField { name: "Lucky Hour", start: 41, bits: 5, range: "0-23", mapping: "value % 24" },
+    BitField { name: "Lucky Minute", start: 46, bits: 6, range: "0-59", mapping: "value % 60" },
+    BitField { name: "Lucky Power of 2", start: 52, bits: 3, range: "2^n (1,2,4,8,16,32,64,128)", mapping: "1 << value" },
+    BitField { name: "Lucky ASCII", start: 55, bits: 7, range: "printable ASCII (32-126)", mapping: "32 + value % 95" },
+    BitField { name: "Lucky Logic Gate", start: 62, bits: 3, range: "AND,OR,XOR,NOT,NAND,NOR,XNOR,BUFFER", mapping: "GATES[value % 8]" },
+    BitField { name: "Luck Scores", start: 65, bits: 128, range: "16 × 8bit scores", mapping: "value * 100 / 255 each" },
+    BitField { name: "Entropy", start: 193, bits: 12, range: "checksum display", mapping: "value as hex" },
+    BitField { name: "Lucky Emoji", start: 205, bits: 6, range: "U+1F600-1F63F (64 smileys)", mapping: "U+1F600 + value % 64" },
+    BitField { name: "Lucky Direction", start: 211, bits: 3, range: "↑↗→↘↓↙←↖", mapping: "DIRS[value % 8]" },
+    BitField { name: "Lucky Element", start: 214, bits: 4, range: "H(1),He(2),C(6)...U(92)", mapping: "ELEMENTS[value % 16]" },
+    BitField { name: "Lucky Percent", start: 218, bits: 7, range: "0-100%", mapping: "value % 101" },
+    BitField { name: "Lucky Latitude", start: 225, bits: 8, range: "-90° to 90°", mapping: "value % 181 - 90" },
+    BitField { name: "Lucky Longitude", start: 233, bits: 9, range: "-180° to 180°", mapping: "value % 361 - 180" },
+    BitField { name: "Reserved", start: 242, bits: 14, range: "unused", mapping: "unused" },
+];
+
 impl HashBits {
+    /// Plain SHA-256 with the original scheme 1 input.
     pub fn from_seed(year: u32, user: &str) -> Self {
-        let seed = format!("{}-{}-{}", year, user, SALT);
-        let mut hasher = Sha256::new();
-        hasher.update(seed.as_bytes());
-        let result = hasher.finalize();
+        Self::derive(year, user, &DeriveOptions::default())
+    }
+
+    /// SHA-256 of the encoded input, or HMAC-SHA256 when a key is given
+    /// (a private secret, so the fortune cannot be computed from the seed alone).
+    /// Without a namespace or beacon the input is the same as before they existed.
+    pub fn derive(year: u32, user: &str, options: &DeriveOptions) -> Self {
+        let input = Self::input(year, user, options);
+        let result = match options.key {
+            Some(key) => {
+                let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+                mac.update(&input);
+                mac.finalize().into_bytes()
+            }
+            None => Sha256::digest(&input),
+        };
         let mut bytes = [0u8; 32];
         bytes.copy_from_slice(&result);
-        Self { bytes }
+        Self {
+            bytes,
+            scheme: options.effective_scheme(),
+            namespace: options.namespace.map(str::to_string),
+            beacon: options.beacon.cloned(),
+        }
+    }
+
+    /// The digest of a saved fingerprint (64 hex digits), to re-derive its
+    /// fields. The scheme, namespace and beacon can't be recovered from
+    /// the digest and are left at their defaults.
+    pub fn from_fingerprint(hex: &str) -> Result<Self, String> {
+        let invalid = || format!("Invalid fingerprint: {}", hex);
+        if hex.len() != 64 || !hex.is_ascii() {
+            return Err(invalid());
+        }
+        let mut bytes = [0u8; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).map_err(|_| invalid())?;
+        }
+        Ok(Self {
+            bytes,
+            scheme: HashScheme::V1,
+            namespace: None,
+            beacon: None,
+        })
+    }
+
+    /// The bytes that are hashed (or HMAC'd) for this year, seed and options.
+    pub fn input(year: u32, user: &str, options: &DeriveOptions) -> Vec<u8> {
+        let beacon = options.beacon.map(Beacon::input_field);
+        let mut fields = vec![user, SALT];
+        // Trailing fields are positional: a beacon without a namespace
+        // keeps an empty namespace slot so it can't pass for one.
+        // Either one makes the effective scheme length-prefixed.
+        if options.namespace.is_some() || beacon.is_some() {
+            fields.push(options.namespace.unwrap_or(""));
+        }
+        fields.extend(beacon.as_deref());
+        encode_input(options.effective_scheme(), year, &fields)
+    }
+
+    pub fn scheme(&self) -> HashScheme {
+        self.scheme
+    }
+
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    pub fn beacon(&self) -> Option<&Beacon> {
+        self.beacon.as_ref()
     }
 
     pub fn hex_string(&self) -> String {
         self.bytes.iter().map(|b| format!("{:02x}", b)).collect()
     }
 
-    fn get_bits(&self, start_bit: usize, num_bits: usize) -> u64 {
+    pub fn bytes(&self) -> &[u8; 32] {
+        &self.bytes
+    }
+
+    /// `num_bits` bits (at most 64) from `start_bit`, most significant first.
+    pub fn get_bits(&self, start_bit: usize, num_bits: usize) -> u64 {
         let mut result: u64 = 0;
         for i in 0..num_bits {
             let bit_index = start_bit + i;
@@ -89,6 +296,7 @@ impl HashBits {
     }
 
     /// bit[65..192]: Luck Scores (128bit = 8bit x 16)
+    #[allow(clippy::needless_range_loop)]
     pub fn luck_scores(&self) -> [u8; 16] {
         let mut scores = [0u8; 16];
         for i in 0..16 {
@@ -127,6 +335,16 @@ impl HashBits {
         ELEMENTS[value % 16]
     }
 
+    /// The Lucky Element as (symbol, atomic number)
+    pub fn lucky_atom(&self) -> (&'static str, u8) {
+        const ATOMS: [(&str, u8); 16] = [
+            ("H", 1), ("He", 2), ("C", 6), ("N", 7), ("O", 8), ("Na", 11), ("Mg", 12), ("Al", 13),
+            ("Si", 14), ("Fe", 26), ("Cu", 29), ("Ag", 47), ("Au", 79), ("Pt", 78), ("Pb", 82), ("U", 92),
+        ];
+        let value = self.get_bits(214, 4) as usize;
+        ATOMS[value % 16]
+    }
+
     /// bit[218..224]: Lucky Percent (7bit) -> 0-100 (101 values, fair distribution)
     pub fn lucky_percent(&self) -> u8 {
         let value = self.get_bits(218, 7) as u8;
@@ -145,11 +363,37 @@ impl HashBits {
         (value % 361) as i16 - 180
     }
 
+    /// Lucky Color as RGB: (Lucky Hex, Lucky Number, their wrapping sum / 2)
+    pub fn lucky_rgb(&self) -> [u8; 3] {
+        let (hex, number) = (self.lucky_hex(), self.lucky_number());
+        [hex, number, hex.wrapping_add(number) / 2]
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::luck::{LuckScore, LuckType};
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_from_fingerprint_round_trip() {
+        let hash = HashBits::from_seed(2026, "alice");
+        let parsed = HashBits::from_fingerprint(&hash.hex_string()).unwrap();
+        assert_eq!(parsed.bytes(), hash.bytes());
+        assert!(HashBits::from_fingerprint("abc").is_err());
+        assert!(HashBits::from_fingerprint(&"zz".repeat(32)).is_err());
+        assert!(HashBits::from_fingerprint(&"é".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn test_lucky_atom_matches_element() {
+        for i in 0..100 {
+            let hash = HashBits::from_seed(2026, &format!("test-{}", i));
+            let (symbol, number) = hash.lucky_atom();
+            assert_eq!(hash.lucky_element(), format!("{} ({})", symbol, number));
+        }
+    }
 
     #[test]
     fn test_hash_deterministic() {
@@ -172,7 +416,196 @@ mod tests {
         assert_ne!(hash1.hex_string(), hash2.hex_string());
     }
 
+    fn scheme(scheme: HashScheme) -> DeriveOptions<'static> {
+        DeriveOptions { scheme, ..Default::default() }
+    }
+
+    fn keyed(key: &[u8]) -> DeriveOptions<'_> {
+        DeriveOptions { key: Some(key), ..Default::default() }
+    }
+
+    #[test]
+    fn test_keyed_hash_depends_on_key() {
+        let plain = HashBits::from_seed(2026, "alice");
+        let keyed1 = HashBits::derive(2026, "alice", &keyed(b"secret-1"));
+        let keyed2 = HashBits::derive(2026, "alice", &keyed(b"secret-2"));
+        assert_ne!(plain.hex_string(), keyed1.hex_string());
+        assert_ne!(keyed1.hex_string(), keyed2.hex_string());
+        assert_eq!(
+            keyed1.hex_string(),
+            HashBits::derive(2026, "alice", &keyed(b"secret-1")).hex_string()
+        );
+    }
+
+    #[test]
+    fn test_scheme_v1_is_legacy_input() {
+        let hash = HashBits::derive(2026, "test-user", &scheme(HashScheme::V1));
+        assert_eq!(hash.hex_string(), HashBits::from_seed(2026, "test-user").hex_string());
+        assert_eq!(hash.scheme(), HashScheme::V1);
+    }
+
+    #[test]
+    fn test_scheme_v2_differs_from_v1() {
+        let v1 = HashBits::derive(2026, "alice", &scheme(HashScheme::V1));
+        let v2 = HashBits::derive(2026, "alice", &scheme(HashScheme::V2));
+        assert_ne!(v1.hex_string(), v2.hex_string());
+        assert_eq!(v2.scheme(), HashScheme::V2);
+    }
+
+    #[test]
+    fn test_namespace_changes_fortune() {
+        let acme = DeriveOptions { namespace: Some("acme"), ..Default::default() };
+        let globex = DeriveOptions { namespace: Some("globex"), ..Default::default() };
+        let plain = HashBits::from_seed(2026, "alice");
+        let hash = HashBits::derive(2026, "alice", &acme);
+        assert_ne!(hash.hex_string(), plain.hex_string());
+        assert_ne!(hash.hex_string(), HashBits::derive(2026, "alice", &globex).hex_string());
+        assert_eq!(hash.namespace(), Some("acme"));
+        assert_eq!(plain.namespace(), None);
+    }
+
+    #[test]
+    fn test_namespace_is_a_separate_v2_field() {
+        assert_ne!(
+            encode_input(HashScheme::V2, 2026, &["alice", SALT]),
+            encode_input(HashScheme::V2, 2026, &["alice", SALT, ""])
+        );
+    }
+
     #[test]
+    fn test_namespace_forces_scheme_v2() {
+        let acme = DeriveOptions { namespace: Some("acme"), ..Default::default() };
+        let v2 = DeriveOptions { scheme: HashScheme::V2, ..acme };
+        let hash = HashBits::derive(2026, "alice", &acme);
+        assert_eq!(hash.scheme(), HashScheme::V2);
+        assert_eq!(hash.hex_string(), HashBits::derive(2026, "alice", &v2).hex_string());
+    }
+
+    #[test]
+    fn test_namespace_cannot_collide_with_seed() {
+        // Under scheme 1 both would hash "2026-alice-sha-omikuji-2026-x-sha-omikuji-2026-acme"
+        let seed = format!("alice-{}-x", SALT);
+        let namespace = format!("x-{}-acme", SALT);
+        let a = DeriveOptions { namespace: Some("acme"), ..Default::default() };
+        let b = DeriveOptions { namespace: Some(&namespace), ..Default::default() };
+        assert_ne!(HashBits::input(2026, &seed, &a), HashBits::input(2026, "alice", &b));
+        assert_ne!(
+            HashBits::derive(2026, &seed, &a).hex_string(),
+            HashBits::derive(2026, "alice", &b).hex_string()
+        );
+    }
+
+    #[test]
+    fn test_beacon_changes_fortune() {
+        let beacon = Beacon {
+            round: 42,
+            randomness: "ab".repeat(32),
+        };
+        let with_beacon = DeriveOptions { beacon: Some(&beacon), ..Default::default() };
+        let hash = HashBits::derive(2026, "alice", &with_beacon);
+        assert_ne!(hash.hex_string(), HashBits::from_seed(2026, "alice").hex_string());
+        assert_eq!(hash.beacon(), Some(&beacon));
+
+        // The beacon can't be mistaken for a namespace with the same text
+        let field = beacon.input_field();
+        let lookalike = DeriveOptions { namespace: Some(&field), ..Default::default() };
+        assert_ne!(hash.hex_string(), HashBits::derive(2026, "alice", &lookalike).hex_string());
+        assert_eq!(hash.scheme(), HashScheme::V2);
+    }
+
+    #[test]
+    fn test_beacon_cannot_collide_with_namespace() {
+        // Under scheme 1 both would hash "2026-alice-sha-omikuji-2026-acme-42:abab…"
+        let beacon = Beacon {
+            round: 42,
+            randomness: "ab".repeat(32),
+        };
+        let with_beacon = DeriveOptions { namespace: Some("acme"), beacon: Some(&beacon), ..Default::default() };
+        let namespace = format!("acme-{}", beacon.input_field());
+        let lookalike = DeriveOptions { namespace: Some(&namespace), ..Default::default() };
+        assert_ne!(HashBits::input(2026, "alice", &with_beacon), HashBits::input(2026, "alice", &lookalike));
+    }
+
+    #[test]
+    fn test_scheme_v1_fields_are_ambiguous() {
+        // Moving a dash between seed and salt keeps the V1 input identical...
+        assert_eq!(
+            encode_input(HashScheme::V1, 2026, &["a-b", "c"]),
+            encode_input(HashScheme::V1, 2026, &["a", "b-c"])
+        );
+        // ...but not the V2 input.
+        assert_ne!(
+            encode_input(HashScheme::V2, 2026, &["a-b", "c"]),
+            encode_input(HashScheme::V2, 2026, &["a", "b-c"])
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_scheme_v2_input_is_injective(
+            a in (any::<u32>(), ".*", ".*"),
+            b in (any::<u32>(), ".*", ".*"),
+        ) {
+            prop_assume!(a != b);
+            prop_assert_ne!(
+                encode_input(HashScheme::V2, a.0, &[&a.1, &a.2]),
+                encode_input(HashScheme::V2, b.0, &[&b.1, &b.2])
+            );
+        }
+
+        #[test]
+        fn prop_scheme_v2_input_is_injective_for_dashed_fields(
+            year in 0u32..3000,
+            joined in "[ab-]{0,12}",
+            i in 0usize..=12,
+            j in 0usize..=12,
+        ) {
+            // Same characters split differently between seed and salt
+            let (i, j) = (i.min(joined.len()), j.min(joined.len()));
+            prop_assume!(i != j);
+            let (seed_a, salt_a) = joined.split_at(i);
+            let (seed_b, salt_b) = joined.split_at(j);
+            prop_assert_ne!(
+                encode_input(HashScheme::V2, year, &[seed_a, salt_a]),
+                encode_input(HashScheme::V2, year, &[seed_b, salt_b])
+            );
+        }
+
+        #[test]
+        fn prop_namespace_never_collides_with_seed(
+            x in ".{0,8}",
+            y in ".{0,8}",
+            z in ".{0,8}",
+            v2 in any::<bool>(),
+        ) {
+            // Scheme 1 would hash both as "{year}-x-{salt}-y-{salt}-z"
+            let scheme = if v2 { HashScheme::V2 } else { HashScheme::V1 };
+            let (seed, namespace) = (format!("{}-{}-{}", x, SALT, y), format!("{}-{}-{}", y, SALT, z));
+            let a = DeriveOptions { scheme, namespace: Some(&z), ..Default::default() };
+            let b = DeriveOptions { scheme, namespace: Some(&namespace), ..Default::default() };
+            prop_assert_ne!(HashBits::input(2026, &seed, &a), HashBits::input(2026, &x, &b));
+        }
+
+        #[test]
+        fn prop_beacon_never_collides_with_namespace(
+            seed in ".{0,8}",
+            namespace in proptest::option::of(".{0,8}"),
+            round in any::<u64>(),
+            v2 in any::<bool>(),
+        ) {
+            // Scheme 1 would hash the beacon as "-{round}:{randomness}"
+            // after the namespace, the same as a longer namespace
+            let scheme = if v2 { HashScheme::V2 } else { HashScheme::V1 };
+            let beacon = Beacon { round, randomness: "ab".repeat(32) };
+            let with_beacon = DeriveOptions { scheme, namespace: namespace.as_deref(), beacon: Some(&beacon), key: None };
+            let lookalike = format!("{}-{}", namespace.as_deref().unwrap_or_default(), beacon.input_field());
+            let without = DeriveOptions { scheme, namespace: Some(&lookalike), beacon: None, key: None };
+            prop_assert_ne!(HashBits::input(2026, &seed, &with_beacon), HashBits::input(2026, &seed, &without));
+        }
+    }
+
+    #[test]
+    #[allow(clippy::manual_range_contains)]
     fn test_lucky_day_range() {
         let hash = HashBits::from_seed(2026, "test");
         let day = hash.lucky_day();
@@ -180,6 +613,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::manual_range_contains)]
     fn test_lucky_day_range_many_seeds() {
         for i in 0..100 {
             let seed = format!("test-{}", i);
@@ -224,6 +658,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(unused_comparisons, clippy::absurd_extreme_comparisons)]
     fn test_lucky_number_range() {
         let hash = HashBits::from_seed(2026, "test");
         let num = hash.lucky_number();
@@ -275,6 +710,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::manual_range_contains)]
     fn test_lucky_ascii_range() {
         for i in 0..100 {
             let seed = format!("test-{}", i);
@@ -296,6 +732,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::manual_range_contains)]
     fn test_lucky_emoji_valid() {
         // Emoji should be in Unicode Emoticons block U+1F600-1F63F (64 smileys)
         for i in 0..200 {
@@ -346,6 +783,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::manual_range_contains)]
     fn test_lucky_latitude_range() {
         for i in 0..100 {
             let seed = format!("test-{}", i);
@@ -356,6 +794,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::manual_range_contains)]
     fn test_lucky_longitude_range() {
         for i in 0..100 {
             let seed = format!("test-{}", i);
@@ -365,4 +804,86 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_layout_covers_all_bits_in_order() {
+        let mut next = 0;
+        for field in LAYOUT {
+            assert_eq!(field.start, next, "{} does not follow the previous field", field.name);
+            next = field.end() + 1;
+        }
+        assert_eq!(next, 256);
+    }
+
+    fn raw(field: &BitField, hash: &HashBits) -> u64 {
+        hash.get_bits(field.start, field.bits)
+    }
+
+    /// Applies each field's documented `mapping` to its documented bits and
+    /// compares the outcome with what the extractor returns.
+    #[test]
+    fn test_layout_matches_extractors() {
+        const ATOMS: [(&str, u8); 16] = [
+            ("H", 1), ("He", 2), ("C", 6), ("N", 7), ("O", 8), ("Na", 11), ("Mg", 12), ("Al", 13),
+            ("Si", 14), ("Fe", 26), ("Cu", 29), ("Ag", 47), ("Au", 79), ("Pt", 78), ("Pb", 82), ("U", 92),
+        ];
+        type Check = fn(&BitField, &HashBits) -> bool;
+        let checks: [(&str, &str, Check); 18] = [
+            ("Lucky Number", "value", |f, h| raw(f, h) == h.lucky_number() as u64),
+            ("Lucky Hex", "value as hex", |f, h| raw(f, h) == h.lucky_hex() as u64),
+            ("Lucky Bits", "value in groups of 4 bits", |f, h| raw(f, h) == h.lucky_bits() as u64),
+            ("Lucky Day", "value % 365 + 1", |f, h| raw(f, h) % 365 + 1 == h.lucky_day() as u64),
+            ("Lucky Hour", "value % 24", |f, h| raw(f, h) % 24 == h.lucky_hour() as u64),
+            ("Lucky Minute", "value % 60", |f, h| raw(f, h) % 60 == h.lucky_minute() as u64),
+            ("Lucky Power of 2", "1 << value", |f, h| 1 << raw(f, h) == h.lucky_power_of_2() as u64),
+            ("Lucky ASCII", "32 + value % 95", |f, h| 32 + raw(f, h) % 95 == h.lucky_ascii() as u64),
+            ("Lucky Logic Gate", "GATES[value % 8]", |f, h| {
+                f.range.split(',').nth(raw(f, h) as usize % 8) == Some(h.lucky_logic_gate())
+            }),
+            ("Luck Scores", "value * 100 / 255 each", |f, h| {
+                (0..16).all(|i| {
+                    let value = h.get_bits(f.start + i * 8, 8);
+                    let score = LuckScore::new(LuckType::ALL[i], h.luck_scores()[i]).score;
+                    value == h.luck_scores()[i] as u64 && score as u64 == value * 100 / 255
+                })
+            }),
+            ("Entropy", "value as hex", |f, h| raw(f, h) == h.entropy_check() as u64),
+            ("Lucky Emoji", "U+1F600 + value % 64", |f, h| {
+                char::from_u32(0x1F600 + (raw(f, h) % 64) as u32) == Some(h.lucky_emoji())
+            }),
+            ("Lucky Direction", "DIRS[value % 8]", |f, h| {
+                f.range.chars().nth(raw(f, h) as usize % 8).map(String::from).as_deref() == Some(h.lucky_direction())
+            }),
+            ("Lucky Element", "ELEMENTS[value % 16]", |f, h| {
+                let (symbol, number) = ATOMS[raw(f, h) as usize % 16];
+                h.lucky_element() == format!("{} ({})", symbol, number) && h.lucky_atom() == (symbol, number)
+            }),
+            ("Lucky Percent", "value % 101", |f, h| raw(f, h) % 101 == h.lucky_percent() as u64),
+            ("Lucky Latitude", "value % 181 - 90", |f, h| (raw(f, h) % 181) as i64 - 90 == h.lucky_latitude() as i64),
+            ("Lucky Longitude", "value % 361 - 180", |f, h| (raw(f, h) % 361) as i64 - 180 == h.lucky_longitude() as i64),
+            ("Reserved", "unused", |_, _| true),
+        ];
+
+        let hashes: Vec<HashBits> = (0..500).map(|i| HashBits::from_seed(2026, &format!("layout-{}", i))).collect();
+        for field in LAYOUT {
+            let (_, mapping, check) = checks
+                .iter()
+                .find(|(name, _, _)| *name == field.name)
+                .unwrap_or_else(|| panic!("no check for {}", field.name));
+            assert_eq!(field.mapping, *mapping, "{} documents a mapping the test does not apply", field.name);
+            for hash in &hashes {
+                assert!(check(&field, hash), "{} disagrees with its layout for {}", field.name, hash.hex_string());
+            }
+        }
+    }
+
+    #[test]
+    fn test_input_is_what_derive_hashes() {
+        let options = DeriveOptions {
+            namespace: Some("acme"),
+            ..Default::default()
+        };
+        let input = HashBits::input(2026, "alice", &options);
+        assert_eq!(input, encode_input(HashScheme::V2, 2026, &["alice", SALT, "acme"]));
+        assert_eq!(HashBits::derive(2026, "alice", &options).bytes()[..], Sha256::digest(&input)[..]);
+    }
 }
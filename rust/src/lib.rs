@@ -0,0 +1,20 @@
+//! Deterministic SHA-256 fortunes: derive `HashBits` from a year and seed,
+//! turn them into an `OmikujiResult`, and render it in any output format.
+//! The `hash-omikuji` binary adds the CLI, seed sources, cards, TUI and server.
+
+pub mod beacon;
+pub mod chart;
+pub mod chat;
+pub mod ffi;
+pub mod format;
+pub mod hash;
+pub mod luck;
+pub mod normalize;
+pub mod output;
+pub mod privacy;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod schema;
+pub mod verify;
+#[cfg(feature = "wasm")]
+pub mod wasm;
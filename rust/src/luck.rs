@@ -1,6 +1,8 @@
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub enum Rank {
     Excellent,
     Good,
@@ -10,6 +12,8 @@ pub enum Rank {
 }
 
 impl Rank {
+    pub const ALL: [Rank; 5] = [Rank::Excellent, Rank::Good, Rank::Normal, Rank::Bad, Rank::Terrible];
+
     pub fn from_score(score: u8) -> Self {
         match score {
             90..=100 => Rank::Excellent,
@@ -31,7 +35,7 @@ impl Rank {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub enum LuckType {
     Life,
     Health,
@@ -92,9 +96,52 @@ impl LuckType {
         }
     }
 
+    pub fn theme(&self) -> LuckTheme {
+        match self {
+            LuckType::Life | LuckType::Health | LuckType::Wealth | LuckType::Windfall => {
+                LuckTheme::Fortune
+            }
+            LuckType::Love | LuckType::Marriage | LuckType::Family | LuckType::Friendship => {
+                LuckTheme::Relationships
+            }
+            LuckType::Career
+            | LuckType::Study
+            | LuckType::Challenge
+            | LuckType::Opportunity
+            | LuckType::Motivation => LuckTheme::Work,
+            LuckType::Debug | LuckType::WiFi | LuckType::Chaos => LuckTheme::Tech,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LuckTheme {
+    Fortune,
+    Relationships,
+    Work,
+    Tech,
 }
 
-#[derive(Debug, Clone, Serialize)]
+impl LuckTheme {
+    pub const ALL: [LuckTheme; 4] = [
+        LuckTheme::Fortune,
+        LuckTheme::Relationships,
+        LuckTheme::Work,
+        LuckTheme::Tech,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LuckTheme::Fortune => "Life, Health & Wealth",
+            LuckTheme::Relationships => "Relationships",
+            LuckTheme::Work => "Work & Growth",
+            LuckTheme::Tech => "Tech",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(try_from = "LuckScoreData")]
 pub struct LuckScore {
     pub luck_type: LuckType,
     pub raw_value: u8,
@@ -102,6 +149,46 @@ pub struct LuckScore {
     pub rank: Rank,
 }
 
+/// Unchecked `LuckScore` as read from JSON; see `TryFrom` below.
+#[derive(Deserialize, JsonSchema)]
+#[schemars(rename = "LuckScore")]
+struct LuckScoreData {
+    luck_type: LuckType,
+    raw_value: u8,
+    score: u8,
+    rank: Rank,
+}
+
+impl TryFrom<LuckScoreData> for LuckScore {
+    type Error = String;
+
+    fn try_from(data: LuckScoreData) -> Result<Self, Self::Error> {
+        if data.score > 100 {
+            return Err(format!("{}: score {} out of range 0-100", data.luck_type.name(), data.score));
+        }
+        let expected = LuckScore::new(data.luck_type, data.raw_value);
+        if data.score != expected.score {
+            return Err(format!(
+                "{}: score {} does not match raw_value {} (expected {})",
+                data.luck_type.name(),
+                data.score,
+                data.raw_value,
+                expected.score
+            ));
+        }
+        if data.rank != expected.rank {
+            return Err(format!(
+                "{}: rank {} does not match score {} (expected {})",
+                data.luck_type.name(),
+                data.rank.as_str(),
+                data.score,
+                expected.rank.as_str()
+            ));
+        }
+        Ok(expected)
+    }
+}
+
 impl LuckScore {
     pub fn new(luck_type: LuckType, raw_value: u8) -> Self {
         let score = ((raw_value as u32) * 100 / 255) as u8;
@@ -123,6 +210,13 @@ pub fn calculate_luck_scores(scores: &[u8; 16]) -> Vec<LuckScore> {
         .collect()
 }
 
+/// Scores ordered best first; ties keep `LuckType::ALL` order.
+pub fn sorted_by_score(scores: &[LuckScore]) -> Vec<&LuckScore> {
+    let mut sorted: Vec<&LuckScore> = scores.iter().collect();
+    sorted.sort_by_key(|score| Reverse(score.score));
+    sorted
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,8 +247,35 @@ mod tests {
         assert_eq!(luck.score, 49);
     }
 
+    #[test]
+    fn test_luck_score_deserialize_round_trip() {
+        let luck = LuckScore::new(LuckType::WiFi, 200);
+        let json = serde_json::to_string(&luck).unwrap();
+        let parsed: LuckScore = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, luck);
+    }
+
+    #[test]
+    fn test_luck_score_deserialize_rejects_inconsistent_values() {
+        let out_of_range = r#"{"luck_type":"Life","raw_value":255,"score":120,"rank":"Excellent"}"#;
+        assert!(serde_json::from_str::<LuckScore>(out_of_range).is_err());
+
+        let wrong_score = r#"{"luck_type":"Life","raw_value":0,"score":99,"rank":"Excellent"}"#;
+        assert!(serde_json::from_str::<LuckScore>(wrong_score).is_err());
+
+        let wrong_rank = r#"{"luck_type":"Life","raw_value":255,"score":100,"rank":"Bad"}"#;
+        assert!(serde_json::from_str::<LuckScore>(wrong_rank).is_err());
+    }
+
     #[test]
     fn test_luck_type_count() {
         assert_eq!(LuckType::ALL.len(), 16);
     }
+
+    #[test]
+    fn test_every_theme_has_luck_types() {
+        for theme in LuckTheme::ALL {
+            assert!(LuckType::ALL.iter().any(|t| t.theme() == theme));
+        }
+    }
 }
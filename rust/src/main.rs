@@ -1,42 +1,344 @@
+mod animate;
+mod art;
+mod avalanche;
+mod batch;
+mod card;
+mod commitment;
+mod completions;
 mod cli;
-mod hash;
-mod luck;
-mod output;
+mod config;
+mod explain;
+mod seed;
+mod serve;
+mod stats;
+mod tui;
 
 use clap::Parser;
-use cli::Args;
-use hash::HashBits;
+use hash_omikuji::{beacon, chart, format, hash, luck, normalize, output, privacy, schema, verify};
+use beacon::Beacon;
+use cli::{Args, Command};
+use config::Config;
+use format::OutputFormat;
+use hash::{DeriveOptions, HashBits};
+use normalize::Normalization;
 use output::OmikujiResult;
+use privacy::SeedDisplay;
+use schema::SchemaVersion;
+use serde::Serialize;
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, BufReader, IsTerminal, Read, Write};
+use std::num::NonZeroUsize;
+use std::path::Path;
 
-fn main() {
-    let args = Args::parse();
+fn fail(msg: impl Display) -> ! {
+    eprintln!("{}", msg);
+    std::process::exit(1);
+}
 
-    // Check if we can execute
-    let show_warning = match args.can_execute() {
-        Ok(warning) => warning,
-        Err(msg) => {
-            eprintln!("{}", msg);
-            std::process::exit(1);
+fn read_input(path: &Path) -> Result<String, String> {
+    if path.as_os_str() == "-" {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|e| format!("Failed to read stdin: {}", e))?;
+        Ok(input)
+    } else {
+        fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+    }
+}
+
+fn print_result(args: &Args, result: &OmikujiResult) {
+    let output_format = args.output_format();
+    if output_format == OutputFormat::Text {
+        let text = result.format_text(args.short, args.show_seed, args.style, args.group);
+        if args.animate && io::stdout().is_terminal() {
+            animate::play(&text, &mut io::stdout(), animate::FRAME_DELAY).expect("Failed to write output");
+        } else {
+            print!("{}", text);
         }
-    };
+    } else {
+        match format::render(result, output_format, args.schema_version.unwrap_or_default()) {
+            Ok(bytes) => {
+                io::stdout().write_all(&bytes).expect("Failed to write output");
+            }
+            Err(msg) => fail(msg),
+        }
+    }
+}
 
-    let year = args.get_year();
+/// Prints a report as text, or in the requested serde format.
+fn print_report<T: Serialize>(args: &Args, report: &T, text: impl FnOnce(&T) -> String) {
+    if args.output_format() == OutputFormat::Text {
+        print!("{}", text(report));
+    } else {
+        let bytes = format::render_value(report, args.output_format()).unwrap_or_else(|msg| fail(msg));
+        io::stdout().write_all(&bytes).expect("Failed to write output");
+    }
+}
 
-    if show_warning && !args.json {
+/// The January 1st gate, for every command that draws this year's fortune:
+/// fails on other days unless `--force-year` is given, and returns the year.
+fn january_first_year(args: &Args) -> u32 {
+    let show_warning = args.can_execute().unwrap_or_else(|msg| fail(msg));
+    let year = args.get_year();
+    if show_warning && args.output_format() == OutputFormat::Text {
         eprintln!("WARNING: Running outside January 1st with --force-year {}.\n", year);
     }
+    year
+}
+
+/// Seed (default: username@hostname, see --seed-source), normalized.
+fn seed(args: &Args) -> (String, Vec<Normalization>) {
+    args.get_seed().unwrap_or_else(|msg| fail(msg))
+}
+
+/// Config, namespace, beacon and key behind every draw.
+struct Context<'a> {
+    args: &'a Args,
+    config: Config,
+    namespace: Option<String>,
+    beacon: Option<Beacon>,
+    key: Option<Vec<u8>>,
+    display: SeedDisplay,
+}
+
+impl<'a> Context<'a> {
+    fn load(args: &'a Args) -> Self {
+        let config_dir = config::config_dir();
+        let config = match config_dir {
+            Some(ref dir) => Config::load(dir).unwrap_or_else(|msg| fail(msg)),
+            None => Config::default(),
+        };
+        let namespace = args.namespace(&config);
+
+        let beacon = args.beacon_file.as_ref().map(|path| {
+            read_input(path)
+                .and_then(|json| Beacon::parse(&json))
+                .unwrap_or_else(|msg| fail(msg))
+        });
+
+        let key = if args.private {
+            let dir = config_dir.unwrap_or_else(|| fail("Cannot determine config directory for the secret key"));
+            Some(privacy::load_or_create_key(&dir).unwrap_or_else(|msg| fail(msg)))
+        } else {
+            None
+        };
+
+        let display = args.seed_display(key.clone());
+        Self {
+            args,
+            config,
+            namespace,
+            beacon,
+            key,
+            display,
+        }
+    }
 
-    // Get seed (default: username@hostname)
-    let seed = args.get_seed();
+    fn options(&self) -> DeriveOptions<'_> {
+        DeriveOptions {
+            scheme: self.args.scheme,
+            namespace: self.namespace.as_deref(),
+            beacon: self.beacon.as_ref(),
+            key: self.key.as_deref(),
+        }
+    }
 
-    // Generate hash and result
-    let hash = HashBits::from_seed(year, &seed);
-    let result = OmikujiResult::from_hash(&hash, year, &seed);
+    fn draw(&self, year: u32, seed: &str) -> (HashBits, OmikujiResult) {
+        let hash = HashBits::derive(year, seed, &self.options());
+        let result = OmikujiResult::from_hash(&hash, year, seed).with_seed_display(seed, &self.display);
+        (hash, result)
+    }
 
-    // Output
-    if args.json {
-        println!("{}", result.format_json());
+    /// Draws a seed as typed, applying `--normalize` first (batch and serve).
+    fn draw_normalized(&self, year: u32, seed: &str) -> OmikujiResult {
+        let (seed, steps) = normalize::normalize(seed, &self.args.normalize);
+        self.draw(year, &seed).1.with_seed_normalization(&steps)
+    }
+}
+
+fn show(args: &Args, file: &Path) {
+    let result = read_input(file)
+        .and_then(|json| OmikujiResult::from_json(&json))
+        .unwrap_or_else(|msg| fail(msg));
+    print_result(args, &result);
+}
+
+fn manpage(out_dir: Option<&Path>) {
+    match out_dir {
+        Some(dir) => completions::write_manpages(dir).unwrap_or_else(|msg| fail(msg)),
+        None => {
+            let pages = completions::manpages().unwrap_or_else(|msg| fail(msg));
+            io::stdout().write_all(&pages[0].1).expect("Failed to write output");
+        }
+    }
+}
+
+fn verify(ctx: &Context, file: &Path) {
+    let args = ctx.args;
+    let fortune = read_input(file)
+        .and_then(|json| OmikujiResult::from_json(&json))
+        .unwrap_or_else(|msg| fail(msg));
+    let seed = args.raw_seed().unwrap_or_else(|msg| fail(msg));
+    let verdict = verify::verify_fortune(&fortune, &seed, ctx.key.as_deref());
+    if args.output_format() == OutputFormat::Text {
+        match verdict {
+            Ok(()) => println!("Fortune verified: {}", fortune.fingerprint),
+            Err(reason) => fail(format!("Verification failed: {}", reason)),
+        }
     } else {
-        print!("{}", result.format_text(args.short, args.show_seed));
+        let report = match verdict {
+            Ok(()) => serde_json::json!({ "valid": true }),
+            Err(ref reason) => serde_json::json!({ "valid": false, "error": reason }),
+        };
+        let bytes = format::render_value(&report, args.output_format()).unwrap_or_else(|msg| fail(msg));
+        io::stdout().write_all(&bytes).expect("Failed to write output");
+        if verdict.is_err() {
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Commits to next year's draw (or this year's on January 1st), so no gate.
+fn commit(ctx: &Context, beacon_round: Option<u64>) {
+    let (seed, _) = seed(ctx.args);
+    let options = ctx.options();
+    let inputs = commitment::DrawInputs {
+        beacon_round: beacon_round.or(options.beacon.map(|beacon| beacon.round)),
+        ..commitment::DrawInputs::new(&seed, ctx.args.commit_year(), &options)
+    };
+    let c = commitment::commit(&inputs).unwrap_or_else(|msg| fail(msg));
+    print_report(ctx.args, &c, commitment::Commitment::format_text);
+}
+
+fn serve(ctx: &Context, host: &str, port: u16, rate_limit: u32) {
+    let year = january_first_year(ctx.args);
+    let draw = |year: u32, seed: &str| ctx.draw_normalized(year, seed);
+    let service = serve::Service {
+        draw: &draw,
+        year,
+        key: ctx.key.as_deref(),
+    };
+    let addr = format!("{}:{}", host, port);
+    serve::run(&addr, &service, serve::RateLimiter::new(rate_limit)).unwrap_or_else(|msg| fail(msg));
+}
+
+fn batch(ctx: &Context, file: &Path, jobs: Option<NonZeroUsize>) {
+    let args = ctx.args;
+    let year = january_first_year(args);
+    let input: Box<dyn io::BufRead> = if file.as_os_str() == "-" {
+        Box::new(io::stdin().lock())
+    } else {
+        let f = fs::File::open(file).unwrap_or_else(|e| fail(format!("Failed to read {}: {}", file.display(), e)));
+        Box::new(BufReader::new(f))
+    };
+    let draw = |year: u32, seed: &str| ctx.draw_normalized(year, seed);
+    let version = args.schema_version.unwrap_or_default();
+    batch::run(input, io::stdout().lock(), &draw, year, args.output_format(), version, jobs)
+        .unwrap_or_else(|msg| fail(msg));
+}
+
+fn explain(ctx: &Context) {
+    let year = january_first_year(ctx.args);
+    let (seed, _) = seed(ctx.args);
+    let options = ctx.options();
+    let hash = HashBits::derive(year, &seed, &options);
+    print!("{}\n{}", explain::derivation(year, &seed, &options), explain::fields(&hash, io::stdout().is_terminal()));
+}
+
+fn avalanche(ctx: &Context, bit: Option<usize>) {
+    let args = ctx.args;
+    let year = january_first_year(args);
+    let (seed, _) = seed(args);
+    // Only echo seeds the user typed or asked to see, never the default username@hostname
+    let hidden = (args.seed.is_none() && !args.show_seed).then_some(&ctx.display);
+    let draw = |year: u32, seed: &str| ctx.draw(year, seed);
+    let avalanche = avalanche::compare(year, &seed, bit, &draw, hidden).unwrap_or_else(|msg| fail(msg));
+    print_report(args, &avalanche, avalanche::Avalanche::format_text);
+}
+
+fn stats(ctx: &Context, samples: u32) {
+    let year = january_first_year(ctx.args);
+    let (seed, normalization) = seed(ctx.args);
+    let result = ctx.draw(year, &seed).1.with_seed_normalization(&normalization);
+    let samples: Vec<OmikujiResult> = (0..samples).map(|i| ctx.draw(year, &format!("sample-{}", i)).1).collect();
+    let stats = stats::compute(&samples, &result);
+    print_report(ctx.args, &stats, stats::Stats::format_text);
+}
+
+fn tui(ctx: &Context, team: &[String]) {
+    let year = january_first_year(ctx.args);
+    let (seed, normalization) = seed(ctx.args);
+    let result = ctx.draw(year, &seed).1.with_seed_normalization(&normalization);
+    let history = (1..=tui::HISTORY_YEARS)
+        .filter_map(|n| year.checked_sub(n))
+        .map(|year| ctx.draw(year, &seed).1.with_seed_normalization(&normalization))
+        .collect();
+    let members = if team.is_empty() { &ctx.config.team } else { team };
+    let team = tui::team_fortunes(members, year, &ctx.options(), &ctx.args.normalize, &ctx.display);
+    tui::run(tui::App::new(result, history, team)).unwrap_or_else(|msg| fail(msg));
+}
+
+/// Checks the commitment, then draws as usual.
+fn reveal(ctx: &Context, nonce: &str, commitment: &str) {
+    let year = january_first_year(ctx.args);
+    let (seed, normalization) = seed(ctx.args);
+    let inputs = commitment::DrawInputs::new(&seed, year, &ctx.options());
+    commitment::verify(&inputs, nonce, commitment).unwrap_or_else(|msg| fail(msg));
+    eprintln!("Commitment verified: {}\n", commitment.trim());
+    print_draw(ctx, year, &seed, &normalization);
+}
+
+fn draw(ctx: &Context) {
+    let year = january_first_year(ctx.args);
+    let (seed, normalization) = seed(ctx.args);
+    print_draw(ctx, year, &seed, &normalization);
+}
+
+fn print_draw(ctx: &Context, year: u32, seed: &str, normalization: &[Normalization]) {
+    let args = ctx.args;
+    let (hash, result) = ctx.draw(year, seed);
+    let result = result.with_seed_normalization(normalization);
+
+    if let Some(ref path) = args.output {
+        card::write_card(&result, path).unwrap_or_else(|msg| fail(msg));
+    }
+
+    if let Some(ref path) = args.identicon {
+        art::write_identicon(&hash, path).unwrap_or_else(|msg| fail(msg));
+    }
+
+    // Output
+    print_result(args, &result);
+    if args.explain && args.output_format() == OutputFormat::Text {
+        print!("\n{}", explain::fields(&hash, io::stdout().is_terminal()));
+    }
+    if args.art && args.output_format() == OutputFormat::Text {
+        print!("\n{}", art::randomart(&hash, &format!("Omikuji {}", year)));
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+
+    match args.command {
+        Some(Command::Schema) => println!("{}", schema::json_schema(args.schema_version.unwrap_or(SchemaVersion::V2))),
+        Some(Command::Layout) => print!("{}", explain::layout()),
+        Some(Command::Completions { shell }) => {
+            io::stdout().write_all(&completions::completions(shell)).expect("Failed to write output")
+        }
+        Some(Command::Manpage { ref out_dir }) => manpage(out_dir.as_deref()),
+        Some(Command::Show { ref file }) => show(&args, file),
+        Some(Command::Verify { ref file }) => verify(&Context::load(&args), file),
+        Some(Command::Commit { beacon_round }) => commit(&Context::load(&args), beacon_round),
+        Some(Command::Reveal { ref nonce, ref commitment }) => reveal(&Context::load(&args), nonce, commitment),
+        Some(Command::Serve { ref host, port, rate_limit }) => serve(&Context::load(&args), host, port, rate_limit),
+        Some(Command::Batch { ref file, jobs }) => batch(&Context::load(&args), file, jobs),
+        Some(Command::Explain) => explain(&Context::load(&args)),
+        Some(Command::Avalanche { bit }) => avalanche(&Context::load(&args), bit),
+        Some(Command::Stats { samples }) => stats(&Context::load(&args), samples),
+        Some(Command::Tui { ref team }) => tui(&Context::load(&args), team),
+        Some(Command::Draw) | None => draw(&Context::load(&args)),
     }
 }
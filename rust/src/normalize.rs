@@ -0,0 +1,126 @@
+use clap::ValueEnum;
+use unicode_normalization::UnicodeNormalization;
+
+/// Opt-in seed clean-up steps. They always run in this declaration
+/// order, whatever order they are given on the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum Normalization {
+    /// Unicode NFC, so composed and decomposed names match
+    Nfc,
+    /// Strip surrounding whitespace and collapse inner runs to one space
+    Trim,
+    /// Full Unicode case folding, so "STRASSE" matches "straße"
+    Casefold,
+    /// For "local@domain": lowercase the domain, drop "+tag", and drop
+    /// dots in Gmail local parts
+    Email,
+}
+
+impl Normalization {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Normalization::Nfc => "nfc",
+            Normalization::Trim => "trim",
+            Normalization::Casefold => "casefold",
+            Normalization::Email => "email",
+        }
+    }
+}
+
+fn canonicalize_email(seed: &str) -> String {
+    let Some((local, domain)) = seed.rsplit_once('@') else {
+        return seed.to_string();
+    };
+    let domain = match domain.to_lowercase().as_str() {
+        "googlemail.com" => "gmail.com".to_string(),
+        other => other.to_string(),
+    };
+    let local = local.split_once('+').map_or(local, |(base, _tag)| base);
+    let local = if domain == "gmail.com" { local.replace('.', "") } else { local.to_string() };
+    format!("{}@{}", local, domain)
+}
+
+/// Applies `steps` (deduplicated, in canonical order) and returns the
+/// normalized seed together with the steps actually applied.
+pub fn normalize(seed: &str, steps: &[Normalization]) -> (String, Vec<Normalization>) {
+    let mut steps = steps.to_vec();
+    steps.sort();
+    steps.dedup();
+
+    let mut seed = seed.to_string();
+    for step in &steps {
+        seed = match step {
+            Normalization::Nfc => seed.nfc().collect(),
+            Normalization::Trim => seed.split_whitespace().collect::<Vec<_>>().join(" "),
+            // Folding can denormalize, so compose before and after
+            Normalization::Casefold => caseless::default_case_fold_str(&seed.nfc().collect::<String>()).nfc().collect(),
+            Normalization::Email => canonicalize_email(&seed),
+        };
+    }
+    (seed, steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_without_steps_is_identity() {
+        let (seed, steps) = normalize(" Alice ", &[]);
+        assert_eq!(seed, " Alice ");
+        assert!(steps.is_empty());
+    }
+
+    #[test]
+    fn test_normalize_trim_and_casefold() {
+        let all = [Normalization::Trim, Normalization::Casefold];
+        assert_eq!(normalize("Alice", &all).0, normalize("  alice ", &all).0);
+        assert_eq!(normalize("Mary  Ann", &all).0, "mary ann");
+    }
+
+    #[test]
+    fn test_normalize_nfc() {
+        let composed = "J\u{00F6}rg";
+        let decomposed = "Jo\u{0308}rg";
+        assert_ne!(composed, decomposed);
+        assert_eq!(
+            normalize(composed, &[Normalization::Nfc]).0,
+            normalize(decomposed, &[Normalization::Nfc]).0
+        );
+    }
+
+    #[test]
+    fn test_normalize_email() {
+        let steps = [Normalization::Email];
+        assert_eq!(normalize("alice+omikuji@Example.COM", &steps).0, "alice@example.com");
+        assert_eq!(normalize("a.l.ice@googlemail.com", &steps).0, "alice@gmail.com");
+        assert_eq!(normalize("alice", &steps).0, "alice");
+    }
+
+    #[test]
+    fn test_casefold_sharp_s() {
+        let steps = [Normalization::Casefold];
+        assert_eq!(normalize("STRASSE", &steps).0, "strasse");
+        assert_eq!(normalize("Straße", &steps).0, "strasse");
+    }
+
+    #[test]
+    fn test_casefold_final_sigma() {
+        let steps = [Normalization::Casefold];
+        assert_eq!(normalize("ΣΊΣΥΦΟΣ", &steps).0, normalize("σίσυφος", &steps).0);
+        assert_eq!(normalize("σίσυφος", &steps).0, normalize("σίσυφοσ", &steps).0);
+    }
+
+    #[test]
+    fn test_casefold_composes_decomposed_input() {
+        let steps = [Normalization::Casefold];
+        assert_eq!(normalize("J\u{00D6}RG", &steps).0, normalize("Jo\u{0308}rg", &steps).0);
+        assert!(Normalization::from_str("lowercase", false).is_err());
+    }
+
+    #[test]
+    fn test_normalize_steps_are_canonical() {
+        let (_, steps) = normalize("x", &[Normalization::Email, Normalization::Nfc, Normalization::Email]);
+        assert_eq!(steps, vec![Normalization::Nfc, Normalization::Email]);
+    }
+}
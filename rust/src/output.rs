@@ -1,20 +1,30 @@
-use crate::hash::HashBits;
-use crate::luck::{calculate_luck_scores, LuckScore};
+use crate::beacon::Beacon;
+use crate::chart::{render_scores, ScoreStyle};
+use crate::hash::{HashBits, HashScheme};
+use crate::luck::{calculate_luck_scores, LuckScore, LuckType};
+use crate::privacy::{display_fingerprint, SeedDisplay};
+use crate::normalize::Normalization;
 use chrono::NaiveDate;
-use serde::Serialize;
-use sha2::{Digest, Sha256};
-
-fn hash_seed_for_display(seed: &str) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(seed.as_bytes());
-    let result = hasher.finalize();
-    format!("device:{:02x}{:02x}{:02x}{:02x}", result[0], result[1], result[2], result[3])
-}
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(try_from = "OmikujiResultData")]
 pub struct OmikujiResult {
     pub year: u32,
     pub seed: String,
+    /// Organization namespace mixed into the derivation (see `--namespace`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    /// Public randomness round mixed into the derivation (see `--beacon-file`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub beacon: Option<Beacon>,
+    /// Normalization steps applied to the seed before hashing (see `--normalize`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub seed_normalization: Vec<String>,
+    /// Hash input scheme; omitted for the original scheme 1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash_scheme: Option<u8>,
     pub lucky_number: u8,
     pub lucky_hex: String,
     pub lucky_color: String,
@@ -36,6 +46,82 @@ pub struct OmikujiResult {
     pub fingerprint: String,
 }
 
+// Unchecked `OmikujiResult` as read from JSON; see `TryFrom` below.
+// (A plain comment: doc comments here would become the schema description.)
+#[derive(Deserialize, JsonSchema)]
+#[schemars(rename = "OmikujiResult")]
+struct OmikujiResultData {
+    year: u32,
+    seed: String,
+    /// Organization namespace mixed into the derivation (see `--namespace`)
+    #[serde(default)]
+    namespace: Option<String>,
+    /// Public randomness round mixed into the derivation (see `--beacon-file`)
+    #[serde(default)]
+    beacon: Option<Beacon>,
+    /// Normalization steps applied to the seed before hashing (see `--normalize`)
+    #[serde(default)]
+    seed_normalization: Vec<String>,
+    /// Hash input scheme; omitted for the original scheme 1
+    #[serde(default)]
+    hash_scheme: Option<u8>,
+    lucky_number: u8,
+    lucky_hex: String,
+    lucky_color: String,
+    lucky_bits: String,
+    lucky_day: String,
+    lucky_day_number: u16,
+    lucky_time: String,
+    lucky_power_of_2: u8,
+    lucky_ascii: char,
+    lucky_logic_gate: String,
+    lucky_emoji: String,
+    lucky_direction: String,
+    lucky_element: String,
+    lucky_percent: u8,
+    lucky_latitude: i8,
+    lucky_longitude: i16,
+    luck_scores: Vec<LuckScore>,
+    entropy_check: String,
+    fingerprint: String,
+}
+
+impl TryFrom<OmikujiResultData> for OmikujiResult {
+    type Error = String;
+
+    fn try_from(data: OmikujiResultData) -> Result<Self, Self::Error> {
+        let result = Self {
+            year: data.year,
+            seed: data.seed,
+            namespace: data.namespace,
+            beacon: data.beacon,
+            seed_normalization: data.seed_normalization,
+            hash_scheme: data.hash_scheme,
+            lucky_number: data.lucky_number,
+            lucky_hex: data.lucky_hex,
+            lucky_color: data.lucky_color,
+            lucky_bits: data.lucky_bits,
+            lucky_day: data.lucky_day,
+            lucky_day_number: data.lucky_day_number,
+            lucky_time: data.lucky_time,
+            lucky_power_of_2: data.lucky_power_of_2,
+            lucky_ascii: data.lucky_ascii,
+            lucky_logic_gate: data.lucky_logic_gate,
+            lucky_emoji: data.lucky_emoji,
+            lucky_direction: data.lucky_direction,
+            lucky_element: data.lucky_element,
+            lucky_percent: data.lucky_percent,
+            lucky_latitude: data.lucky_latitude,
+            lucky_longitude: data.lucky_longitude,
+            luck_scores: data.luck_scores,
+            entropy_check: data.entropy_check,
+            fingerprint: data.fingerprint,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+}
+
 impl OmikujiResult {
     pub fn from_hash(hash: &HashBits, year: u32, seed: &str) -> Self {
         let lucky_number = hash.lucky_number();
@@ -57,7 +143,8 @@ impl OmikujiResult {
         let entropy = hash.entropy_check();
 
         let lucky_hex = format!("0x{:02X}", lucky_hex_val);
-        let lucky_color = format!("#{:02X}{:02X}{:02X}", lucky_hex_val, lucky_number, (lucky_hex_val.wrapping_add(lucky_number)) / 2);
+        let [r, g, b] = hash.lucky_rgb();
+        let lucky_color = format!("#{:02X}{:02X}{:02X}", r, g, b);
         let lucky_bits = format!(
             "{:04b} {:04b} {:04b} {:04b}",
             (lucky_bits_val >> 12) & 0xF,
@@ -77,7 +164,14 @@ impl OmikujiResult {
 
         Self {
             year,
-            seed: hash_seed_for_display(seed),
+            seed: display_fingerprint(seed, &SeedDisplay::default()),
+            namespace: hash.namespace().map(str::to_string),
+            beacon: hash.beacon().cloned(),
+            seed_normalization: Vec::new(),
+            hash_scheme: match hash.scheme() {
+                HashScheme::V1 => None,
+                scheme => Some(scheme.number()),
+            },
             lucky_number,
             lucky_hex,
             lucky_color,
@@ -100,10 +194,27 @@ impl OmikujiResult {
         }
     }
 
-    pub fn format_text(&self, short: bool, show_seed: bool) -> String {
+    /// Replaces the displayed seed fingerprint (length, encoding, HMAC key).
+    pub fn with_seed_display(mut self, seed: &str, display: &SeedDisplay) -> Self {
+        self.seed = display_fingerprint(seed, display);
+        self
+    }
+
+    /// Records which normalization steps produced the hashed seed.
+    pub fn with_seed_normalization(mut self, steps: &[Normalization]) -> Self {
+        self.seed_normalization = steps.iter().map(|step| step.name().to_string()).collect();
+        self
+    }
+
+    /// " · <namespace>" for headers, or "" without a namespace.
+    pub fn namespace_suffix(&self) -> String {
+        self.namespace.as_ref().map(|ns| format!(" · {}", ns)).unwrap_or_default()
+    }
+
+    pub fn format_text(&self, short: bool, show_seed: bool, style: ScoreStyle, grouped: bool) -> String {
         let mut output = String::new();
 
-        output.push_str(&format!("🎍 Hash-Omikuji {} 🎍\n\n", self.year));
+        output.push_str(&format!("🎍 Hash-Omikuji {}{} 🎍\n\n", self.year, self.namespace_suffix()));
 
         output.push_str(&format!("Lucky Number      : {}\n", self.lucky_number));
         output.push_str(&format!("Lucky Hex         : {}\n", self.lucky_hex));
@@ -126,24 +237,24 @@ impl OmikujiResult {
         output.push('\n');
 
         output.push_str("Luck Scores :\n");
-        let mut sorted_scores: Vec<_> = self.luck_scores.iter().collect();
-        sorted_scores.sort_by(|a, b| b.score.cmp(&a.score));
-
-        let display_count = if short { 5 } else { sorted_scores.len() };
-        for score in sorted_scores.iter().take(display_count) {
-            output.push_str(&format!(
-                "{:18}: {:3} ({})\n",
-                score.luck_type.name(),
-                score.score,
-                score.rank.as_str()
-            ));
-        }
+        let limit = if short { Some(5) } else { None };
+        output.push_str(&render_scores(&self.luck_scores, style, grouped, limit));
         if show_seed {
             output.push_str(&format!(
-                "\n{} | {}\n",
+                "\n{} | {}",
                 self.seed,
                 self.entropy_check
             ));
+            if let Some(scheme) = self.hash_scheme {
+                output.push_str(&format!(" | scheme {}", scheme));
+            }
+            if let Some(ref beacon) = self.beacon {
+                output.push_str(&format!(" | beacon round {}", beacon.round));
+            }
+            output.push('\n');
+            if !self.seed_normalization.is_empty() {
+                output.push_str(&format!("normalized: {}\n", self.seed_normalization.join(", ")));
+            }
         }
 
         output.push_str(&format!("\n[raw hash: {}]\n", self.fingerprint));
@@ -154,11 +265,88 @@ impl OmikujiResult {
     pub fn format_json(&self) -> String {
         serde_json::to_string_pretty(self).unwrap()
     }
+
+    /// Loads a fortune saved with `--json`; deserializing runs `validate`.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| format!("Invalid fortune JSON: {}", e))
+    }
+
+    /// Checks ranges and internal consistency of a fortune: every field is
+    /// re-derived from the fingerprint and must match. Runs on every
+    /// deserialization; individual luck scores are checked by `LuckScore`.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=9999).contains(&self.year) {
+            return Err(format!("year out of range 1-9999: {}", self.year));
+        }
+        if self.fingerprint.len() != 64 || !self.fingerprint.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("fingerprint must be 64 hex chars: {}", self.fingerprint));
+        }
+        if let Some(ref beacon) = self.beacon {
+            beacon.validate()?;
+        }
+        let luck_types: Vec<_> = self.luck_scores.iter().map(|s| s.luck_type).collect();
+        if luck_types != LuckType::ALL {
+            return Err("luck_scores must list every luck type once, in order".to_string());
+        }
+        if !(1..=365).contains(&self.lucky_day_number) {
+            return Err(format!("lucky_day_number out of range 1-365: {}", self.lucky_day_number));
+        }
+        if !self.lucky_power_of_2.is_power_of_two() {
+            return Err(format!("lucky_power_of_2 is not a power of 2: {}", self.lucky_power_of_2));
+        }
+        if !(' '..='~').contains(&self.lucky_ascii) {
+            return Err(format!("lucky_ascii is not printable ASCII: {:?}", self.lucky_ascii));
+        }
+        if self.lucky_percent > 100 {
+            return Err(format!("lucky_percent out of range 0-100: {}", self.lucky_percent));
+        }
+        if !(-90..=90).contains(&self.lucky_latitude) {
+            return Err(format!("lucky_latitude out of range -90-90: {}", self.lucky_latitude));
+        }
+        if !(-180..=180).contains(&self.lucky_longitude) {
+            return Err(format!("lucky_longitude out of range -180-180: {}", self.lucky_longitude));
+        }
+        self.check_derived_fields()
+    }
+
+    /// Compares each field with its value re-derived from the fingerprint.
+    fn check_derived_fields(&self) -> Result<(), String> {
+        let expected = Self::from_hash(&HashBits::from_fingerprint(&self.fingerprint)?, self.year, "");
+        let fields = [
+            ("lucky_number", self.lucky_number.to_string(), expected.lucky_number.to_string()),
+            ("lucky_hex", self.lucky_hex.clone(), expected.lucky_hex),
+            ("lucky_color", self.lucky_color.clone(), expected.lucky_color),
+            ("lucky_bits", self.lucky_bits.clone(), expected.lucky_bits),
+            ("lucky_day", self.lucky_day.clone(), expected.lucky_day),
+            ("lucky_day_number", self.lucky_day_number.to_string(), expected.lucky_day_number.to_string()),
+            ("lucky_time", self.lucky_time.clone(), expected.lucky_time),
+            ("lucky_power_of_2", self.lucky_power_of_2.to_string(), expected.lucky_power_of_2.to_string()),
+            ("lucky_ascii", self.lucky_ascii.to_string(), expected.lucky_ascii.to_string()),
+            ("lucky_logic_gate", self.lucky_logic_gate.clone(), expected.lucky_logic_gate),
+            ("lucky_emoji", self.lucky_emoji.clone(), expected.lucky_emoji),
+            ("lucky_direction", self.lucky_direction.clone(), expected.lucky_direction),
+            ("lucky_element", self.lucky_element.clone(), expected.lucky_element),
+            ("lucky_percent", self.lucky_percent.to_string(), expected.lucky_percent.to_string()),
+            ("lucky_latitude", self.lucky_latitude.to_string(), expected.lucky_latitude.to_string()),
+            ("lucky_longitude", self.lucky_longitude.to_string(), expected.lucky_longitude.to_string()),
+            ("entropy_check", self.entropy_check.clone(), expected.entropy_check),
+        ];
+        for (name, actual, expected) in fields {
+            if actual != expected {
+                return Err(format!("{} {:?} does not match the fingerprint (expected {:?})", name, actual, expected));
+            }
+        }
+        if self.luck_scores != expected.luck_scores {
+            return Err("luck_scores do not match the fingerprint".to_string());
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::hash::DeriveOptions;
 
     fn create_test_result() -> OmikujiResult {
         let hash = HashBits::from_seed(2026, "test-user");
@@ -250,15 +438,15 @@ mod tests {
     #[test]
     fn test_format_text_contains_header() {
         let result = create_test_result();
-        let text = result.format_text(false, false);
+        let text = result.format_text(false, false, ScoreStyle::Plain, false);
         assert!(text.contains("Hash-Omikuji 2026"));
     }
 
     #[test]
     fn test_format_text_short_mode() {
         let result = create_test_result();
-        let text_full = result.format_text(false, false);
-        let text_short = result.format_text(true, false);
+        let text_full = result.format_text(false, false, ScoreStyle::Plain, false);
+        let text_short = result.format_text(true, false, ScoreStyle::Plain, false);
         // Short mode should be shorter or equal
         assert!(text_short.len() <= text_full.len());
     }
@@ -266,8 +454,8 @@ mod tests {
     #[test]
     fn test_format_text_show_seed() {
         let result = create_test_result();
-        let text_with_seed = result.format_text(false, true);
-        let text_without_seed = result.format_text(false, false);
+        let text_with_seed = result.format_text(false, true, ScoreStyle::Plain, false);
+        let text_without_seed = result.format_text(false, false, ScoreStyle::Plain, false);
         assert!(text_with_seed.contains("device:"));
         assert!(text_with_seed.contains("|"));
         assert!(!text_without_seed.contains("device:"));
@@ -354,10 +542,154 @@ mod tests {
         assert!(result.lucky_longitude >= -180 && result.lucky_longitude <= 180);
     }
 
+    #[test]
+    fn test_seed_normalization_recorded() {
+        let result = create_test_result();
+        let json = result.format_json();
+        assert!(!json.contains("seed_normalization"));
+
+        let result = result.with_seed_normalization(&[Normalization::Nfc, Normalization::Trim]);
+        assert_eq!(result.seed_normalization, vec!["nfc", "trim"]);
+        assert!(result.format_json().contains("\"seed_normalization\""));
+        assert!(result.format_text(false, true, ScoreStyle::Plain, false).contains("normalized: nfc, trim"));
+    }
+
+    #[test]
+    fn test_hash_scheme_recorded_only_for_v2() {
+        assert_eq!(create_test_result().hash_scheme, None);
+
+        let options = DeriveOptions {
+            scheme: HashScheme::V2,
+            ..Default::default()
+        };
+        let hash = HashBits::derive(2026, "test-user", &options);
+        let result = OmikujiResult::from_hash(&hash, 2026, "test-user");
+        assert_eq!(result.hash_scheme, Some(2));
+        assert!(result.format_json().contains("\"hash_scheme\": 2"));
+        assert!(result.format_text(false, true, ScoreStyle::Plain, false).contains("| scheme 2"));
+    }
+
+    #[test]
+    fn test_namespace_in_header_and_json() {
+        assert_eq!(create_test_result().namespace, None);
+        assert!(!create_test_result().format_json().contains("namespace"));
+
+        let options = DeriveOptions {
+            namespace: Some("acme"),
+            ..Default::default()
+        };
+        let hash = HashBits::derive(2026, "test-user", &options);
+        let result = OmikujiResult::from_hash(&hash, 2026, "test-user");
+        assert!(result.format_text(false, false, ScoreStyle::Plain, false).starts_with("🎍 Hash-Omikuji 2026 · acme 🎍"));
+        assert!(result.format_json().contains("\"namespace\": \"acme\""));
+        assert_eq!(OmikujiResult::from_json(&result.format_json()).unwrap(), result);
+    }
+
+    #[test]
+    fn test_beacon_recorded_and_validated() {
+        assert!(!create_test_result().format_json().contains("beacon"));
+
+        let beacon = Beacon {
+            round: 42,
+            randomness: "ab".repeat(32),
+        };
+        let options = DeriveOptions {
+            beacon: Some(&beacon),
+            ..Default::default()
+        };
+        let hash = HashBits::derive(2026, "test-user", &options);
+        let result = OmikujiResult::from_hash(&hash, 2026, "test-user");
+        assert_eq!(result.beacon, Some(beacon));
+        assert!(result.format_text(false, true, ScoreStyle::Plain, false).contains("| beacon round 42"));
+        assert_eq!(OmikujiResult::from_json(&result.format_json()).unwrap(), result);
+
+        let json = result.format_json().replace(&"ab".repeat(32), "zz");
+        assert!(OmikujiResult::from_json(&json).is_err());
+    }
+
+    #[test]
+    fn test_from_json_round_trip() {
+        let result = create_test_result();
+        let loaded = OmikujiResult::from_json(&result.format_json()).unwrap();
+        assert_eq!(loaded, result);
+        assert_eq!(
+            loaded.format_text(false, true, ScoreStyle::Plain, false),
+            result.format_text(false, true, ScoreStyle::Plain, false)
+        );
+    }
+
+    #[test]
+    fn test_from_json_rejects_bad_fingerprint() {
+        let mut result = create_test_result();
+        result.fingerprint.truncate(10);
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(OmikujiResult::from_json(&json).is_err());
+    }
+
+    #[test]
+    fn test_from_json_rejects_missing_luck_scores() {
+        let mut result = create_test_result();
+        result.luck_scores.pop();
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(OmikujiResult::from_json(&json).is_err());
+    }
+
+    #[test]
+    fn test_from_json_rejects_inconsistent_rank() {
+        let json = create_test_result().format_json().replacen("\"Bad\"", "\"Excellent\"", 1);
+        let err = OmikujiResult::from_json(&json).unwrap_err();
+        assert!(err.contains("rank"), "Unexpected error: {}", err);
+    }
+
+    #[test]
+    fn test_deserialize_checks_derived_fields() {
+        let result = create_test_result();
+        let json = serde_json::to_value(&result).unwrap();
+        let mut tampered = json.clone();
+        tampered["lucky_day"] = "2026-01-01 (1 / 365)".into();
+        let err = serde_json::from_value::<OmikujiResult>(tampered).unwrap_err().to_string();
+        assert!(err.contains("lucky_day"), "Unexpected error: {}", err);
+
+        let mut tampered = json.clone();
+        tampered["lucky_day_number"] = 1.into();
+        assert!(serde_json::from_value::<OmikujiResult>(tampered).is_err());
+
+        let mut tampered = json.clone();
+        tampered["lucky_color"] = "#000000".into();
+        assert!(serde_json::from_value::<OmikujiResult>(tampered).is_err());
+
+        let mut tampered = json;
+        tampered["year"] = 0.into();
+        assert!(serde_json::from_value::<OmikujiResult>(tampered).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_accepts_any_seed_and_metadata() {
+        let mut result = create_test_result();
+        result.seed = "hidden".to_string();
+        result.seed_normalization = vec!["nfc".to_string()];
+        let loaded: OmikujiResult = serde_json::from_str(&result.format_json()).unwrap();
+        assert_eq!(loaded, result);
+    }
+
     #[test]
     fn test_snapshot_text_output() {
         let result = create_test_result();
-        let text = result.format_text(false, true);
+        let text = result.format_text(false, true, ScoreStyle::Plain, false);
+        insta::assert_snapshot!(text);
+    }
+
+    #[test]
+    fn test_snapshot_text_output_bars_grouped() {
+        let result = create_test_result();
+        let text = result.format_text(false, false, ScoreStyle::Bars, true);
+        insta::assert_snapshot!(text);
+    }
+
+    #[test]
+    fn test_snapshot_text_output_radar() {
+        let result = create_test_result();
+        let text = result.format_text(false, false, ScoreStyle::Radar, false);
         insta::assert_snapshot!(text);
     }
 
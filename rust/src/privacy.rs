@@ -0,0 +1,171 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use clap::ValueEnum;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+
+pub const KEY_LEN: usize = 32;
+const KEY_FILE: &str = "secret.key";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum FingerprintFormat {
+    /// Lowercase hex, e.g. "device:a3f2b1c9"
+    #[default]
+    Hex,
+    /// URL-safe base64 without padding, e.g. "device:o_KxyQ"
+    Base64,
+}
+
+/// How the seed is shown in the output ("device:..." fingerprint).
+#[derive(Debug, Clone)]
+pub struct SeedDisplay {
+    /// Number of digest bytes to show (1-32)
+    pub length: usize,
+    pub format: FingerprintFormat,
+    /// When set, the fingerprint is an HMAC so it can't be brute-forced
+    /// back to a guessable seed like username@hostname.
+    pub key: Option<Vec<u8>>,
+}
+
+impl Default for SeedDisplay {
+    fn default() -> Self {
+        Self {
+            length: 4,
+            format: FingerprintFormat::Hex,
+            key: None,
+        }
+    }
+}
+
+pub fn display_fingerprint(seed: &str, display: &SeedDisplay) -> String {
+    let digest: Vec<u8> = match display.key {
+        Some(ref key) => {
+            let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+            mac.update(b"display:");
+            mac.update(seed.as_bytes());
+            mac.finalize().into_bytes().to_vec()
+        }
+        None => Sha256::digest(seed.as_bytes()).to_vec(),
+    };
+    let bytes = &digest[..display.length.clamp(1, digest.len())];
+    let encoded = match display.format {
+        FingerprintFormat::Hex => bytes.iter().map(|b| format!("{:02x}", b)).collect(),
+        FingerprintFormat::Base64 => URL_SAFE_NO_PAD.encode(bytes),
+    };
+    format!("device:{}", encoded)
+}
+
+/// Reads the secret key from `dir`, generating and saving a new random
+/// key on first use. The key file holds 64 hex chars.
+pub fn load_or_create_key(dir: &Path) -> Result<Vec<u8>, String> {
+    let path = dir.join(KEY_FILE);
+    if path.exists() {
+        let text = fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        return parse_key(text.trim()).ok_or_else(|| format!("Invalid secret key in {}", path.display()));
+    }
+
+    let mut key = vec![0u8; KEY_LEN];
+    getrandom::fill(&mut key).map_err(|e| format!("Failed to generate secret key: {}", e))?;
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let hex: String = key.iter().map(|b| format!("{:02x}", b)).collect();
+    write_private(&path, &format!("{}\n", hex))?;
+    Ok(key)
+}
+
+fn parse_key(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() != KEY_LEN * 2 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(unix)]
+fn write_private(path: &Path, contents: &str) -> Result<(), String> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(not(unix))]
+fn write_private(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("hash-omikuji-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_default_display_matches_legacy_format() {
+        let display = display_fingerprint("test-user", &SeedDisplay::default());
+        assert_eq!(display, "device:f85ac825");
+    }
+
+    #[test]
+    fn test_display_length_and_format() {
+        let display = SeedDisplay {
+            length: 8,
+            ..Default::default()
+        };
+        assert_eq!(display_fingerprint("alice", &display).len(), "device:".len() + 16);
+
+        let display = SeedDisplay {
+            length: 6,
+            format: FingerprintFormat::Base64,
+            key: None,
+        };
+        assert_eq!(display_fingerprint("alice", &display).len(), "device:".len() + 8);
+    }
+
+    #[test]
+    fn test_keyed_display_hides_plain_digest() {
+        let plain = display_fingerprint("alice", &SeedDisplay::default());
+        let keyed = display_fingerprint(
+            "alice",
+            &SeedDisplay {
+                key: Some(vec![7; KEY_LEN]),
+                ..Default::default()
+            },
+        );
+        assert_ne!(plain, keyed);
+    }
+
+    #[test]
+    fn test_key_created_once_and_reused() {
+        let dir = temp_dir("key");
+        let key1 = load_or_create_key(&dir).unwrap();
+        let key2 = load_or_create_key(&dir).unwrap();
+        assert_eq!(key1.len(), KEY_LEN);
+        assert_eq!(key1, key2);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_key_file_rejected() {
+        let dir = temp_dir("bad-key");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(KEY_FILE), "not-hex").unwrap();
+        assert!(load_or_create_key(&dir).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
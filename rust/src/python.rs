@@ -0,0 +1,347 @@
+use crate::hash::{self, DeriveOptions, HashBits, HashScheme};
+use crate::luck::LuckType;
+use crate::output::OmikujiResult;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
+
+fn derive(seed: &str, year: u32, scheme: u8, namespace: Option<&str>) -> PyResult<HashBits> {
+    if !(1..=9999).contains(&year) {
+        return Err(PyValueError::new_err(format!("year must be 1-9999: {}", year)));
+    }
+    let scheme = HashScheme::from_number(scheme).map_err(PyValueError::new_err)?;
+    let options = DeriveOptions {
+        scheme,
+        namespace: hash::non_empty_namespace(namespace),
+        ..Default::default()
+    };
+    Ok(HashBits::derive(year, seed, &options))
+}
+
+fn fortune(seed: &str, year: u32, scheme: u8, namespace: Option<&str>) -> PyResult<OmikujiResult> {
+    let hash = derive(seed, year, scheme, namespace)?;
+    Ok(OmikujiResult::from_hash(&hash, year, seed))
+}
+
+/// `HashBits(seed, year, scheme=1, namespace=None)`: the raw 256 bits and
+/// the values extracted from them.
+#[pyclass(name = "HashBits", module = "hash_omikuji", frozen)]
+struct PyHashBits(HashBits);
+
+#[pymethods]
+impl PyHashBits {
+    #[new]
+    #[pyo3(signature = (seed, year, scheme=1, namespace=None))]
+    fn new(seed: &str, year: u32, scheme: u8, namespace: Option<&str>) -> PyResult<Self> {
+        derive(seed, year, scheme, namespace).map(Self)
+    }
+
+    #[getter]
+    fn scheme(&self) -> u8 {
+        self.0.scheme().number()
+    }
+
+    #[getter]
+    fn namespace(&self) -> Option<&str> {
+        self.0.namespace()
+    }
+
+    fn hex(&self) -> String {
+        self.0.hex_string()
+    }
+
+    fn to_bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, self.0.bytes())
+    }
+
+    #[getter]
+    fn lucky_number(&self) -> u8 {
+        self.0.lucky_number()
+    }
+
+    #[getter]
+    fn lucky_hex(&self) -> u8 {
+        self.0.lucky_hex()
+    }
+
+    #[getter]
+    fn lucky_bits(&self) -> u16 {
+        self.0.lucky_bits()
+    }
+
+    #[getter]
+    fn lucky_day(&self) -> u16 {
+        self.0.lucky_day()
+    }
+
+    #[getter]
+    fn lucky_hour(&self) -> u8 {
+        self.0.lucky_hour()
+    }
+
+    #[getter]
+    fn lucky_minute(&self) -> u8 {
+        self.0.lucky_minute()
+    }
+
+    #[getter]
+    fn lucky_power_of_2(&self) -> u8 {
+        self.0.lucky_power_of_2()
+    }
+
+    #[getter]
+    fn lucky_ascii(&self) -> char {
+        self.0.lucky_ascii()
+    }
+
+    #[getter]
+    fn lucky_logic_gate(&self) -> &'static str {
+        self.0.lucky_logic_gate()
+    }
+
+    #[getter]
+    fn lucky_emoji(&self) -> char {
+        self.0.lucky_emoji()
+    }
+
+    #[getter]
+    fn lucky_direction(&self) -> &'static str {
+        self.0.lucky_direction()
+    }
+
+    #[getter]
+    fn lucky_element(&self) -> &'static str {
+        self.0.lucky_element()
+    }
+
+    #[getter]
+    fn lucky_percent(&self) -> u8 {
+        self.0.lucky_percent()
+    }
+
+    #[getter]
+    fn lucky_latitude(&self) -> i8 {
+        self.0.lucky_latitude()
+    }
+
+    #[getter]
+    fn lucky_longitude(&self) -> i16 {
+        self.0.lucky_longitude()
+    }
+
+    #[getter]
+    fn entropy_check(&self) -> u16 {
+        self.0.entropy_check()
+    }
+
+    /// Raw 8-bit luck values (before the 0-100 mapping), in `LuckType` order.
+    #[getter]
+    fn luck_bytes(&self) -> [u8; 16] {
+        self.0.luck_scores()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("HashBits('{}')", self.0.hex_string())
+    }
+}
+
+/// A drawn fortune. Fields are read-only; `to_dict()` gives the same
+/// structure as `hash-omikuji --json`.
+#[pyclass(name = "OmikujiResult", module = "hash_omikuji", frozen)]
+struct PyOmikujiResult(OmikujiResult);
+
+#[pymethods]
+impl PyOmikujiResult {
+    #[getter]
+    fn year(&self) -> u32 {
+        self.0.year
+    }
+
+    #[getter]
+    fn seed(&self) -> &str {
+        &self.0.seed
+    }
+
+    #[getter]
+    fn namespace(&self) -> Option<&str> {
+        self.0.namespace.as_deref()
+    }
+
+    #[getter]
+    fn lucky_number(&self) -> u8 {
+        self.0.lucky_number
+    }
+
+    #[getter]
+    fn lucky_hex(&self) -> &str {
+        &self.0.lucky_hex
+    }
+
+    #[getter]
+    fn lucky_color(&self) -> &str {
+        &self.0.lucky_color
+    }
+
+    #[getter]
+    fn lucky_day(&self) -> &str {
+        &self.0.lucky_day
+    }
+
+    #[getter]
+    fn lucky_day_number(&self) -> u16 {
+        self.0.lucky_day_number
+    }
+
+    #[getter]
+    fn lucky_time(&self) -> &str {
+        &self.0.lucky_time
+    }
+
+    #[getter]
+    fn lucky_emoji(&self) -> &str {
+        &self.0.lucky_emoji
+    }
+
+    #[getter]
+    fn lucky_element(&self) -> &str {
+        &self.0.lucky_element
+    }
+
+    #[getter]
+    fn lucky_percent(&self) -> u8 {
+        self.0.lucky_percent
+    }
+
+    #[getter]
+    fn fingerprint(&self) -> &str {
+        &self.0.fingerprint
+    }
+
+    /// Luck scores by name, e.g. {"Life Luck": 42, ...}.
+    fn scores<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        for score in &self.0.luck_scores {
+            dict.set_item(score.luck_type.name(), score.score)?;
+        }
+        Ok(dict)
+    }
+
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        py.import("json")?.call_method1("loads", (self.0.format_json(),))
+    }
+
+    fn to_json(&self) -> String {
+        self.0.format_json()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("OmikujiResult(year={}, fingerprint='{}')", self.0.year, self.0.fingerprint)
+    }
+}
+
+/// The fortune for one seed, same as `hash-omikuji --seed <seed> --force-year <year>`.
+#[pyfunction]
+#[pyo3(signature = (seed, year, scheme=1, namespace=None))]
+fn draw(seed: &str, year: u32, scheme: u8, namespace: Option<&str>) -> PyResult<PyOmikujiResult> {
+    fortune(seed, year, scheme, namespace).map(PyOmikujiResult)
+}
+
+/// Fortunes for many seeds in one call, in input order. The GIL is released
+/// while hashing.
+#[pyfunction]
+#[pyo3(signature = (seeds, year, scheme=1, namespace=None))]
+fn draw_many(py: Python<'_>, seeds: Vec<String>, year: u32, scheme: u8, namespace: Option<&str>) -> PyResult<Vec<PyOmikujiResult>> {
+    py.allow_threads(|| seeds.iter().map(|seed| fortune(seed, year, scheme, namespace).map(PyOmikujiResult)).collect())
+}
+
+/// Names of the luck scores in `HashBits.luck_bytes` order.
+#[pyfunction]
+fn luck_types() -> Vec<&'static str> {
+    LuckType::ALL.iter().map(LuckType::name).collect()
+}
+
+#[pymodule]
+fn hash_omikuji(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyHashBits>()?;
+    m.add_class::<PyOmikujiResult>()?;
+    m.add_function(wrap_pyfunction!(draw, m)?)?;
+    m.add_function(wrap_pyfunction!(draw_many, m)?)?;
+    m.add_function(wrap_pyfunction!(luck_types, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::types::PyList;
+
+    fn with_module<F: FnOnce(Python<'_>, &Bound<'_, PyModule>)>(f: F) {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let module = PyModule::new(py, "hash_omikuji").unwrap();
+            hash_omikuji(&module).unwrap();
+            f(py, &module);
+        });
+    }
+
+    #[test]
+    fn test_to_dict_matches_cli_json() {
+        with_module(|py, module| {
+            let result = module.getattr("draw").unwrap().call1(("test-user", 2026)).unwrap();
+            let dict = result.call_method0("to_dict").unwrap();
+            let json: String = py.import("json").unwrap().call_method1("dumps", (dict,)).unwrap().extract().unwrap();
+            let expected = OmikujiResult::from_hash(&HashBits::from_seed(2026, "test-user"), 2026, "test-user");
+            assert_eq!(serde_json::from_str::<serde_json::Value>(&json).unwrap(), serde_json::to_value(&expected).unwrap());
+        });
+    }
+
+    #[test]
+    fn test_hash_bits_fields() {
+        with_module(|_, module| {
+            let bits = module.getattr("HashBits").unwrap().call1(("test-user", 2026)).unwrap();
+            let hash = HashBits::from_seed(2026, "test-user");
+            assert_eq!(bits.call_method0("hex").unwrap().extract::<String>().unwrap(), hash.hex_string());
+            assert_eq!(bits.getattr("lucky_number").unwrap().extract::<u8>().unwrap(), hash.lucky_number());
+            assert_eq!(bits.getattr("luck_bytes").unwrap().extract::<[u8; 16]>().unwrap(), hash.luck_scores());
+        });
+    }
+
+    #[test]
+    fn test_empty_namespace_is_no_namespace() {
+        with_module(|_, module| {
+            let hash_bits = module.getattr("HashBits").unwrap();
+            let empty = hash_bits.call1(("alice", 2026, 1, "")).unwrap();
+            let none = hash_bits.call1(("alice", 2026)).unwrap();
+            assert_eq!(
+                empty.call_method0("hex").unwrap().extract::<String>().unwrap(),
+                none.call_method0("hex").unwrap().extract::<String>().unwrap()
+            );
+            assert!(empty.getattr("namespace").unwrap().is_none());
+        });
+    }
+
+    #[test]
+    fn test_draw_many_keeps_order() {
+        with_module(|py, module| {
+            let seeds = PyList::new(py, ["alice", "bob", "carol"]).unwrap();
+            let results = module.getattr("draw_many").unwrap().call1((seeds, 2026)).unwrap();
+            let fingerprints: Vec<String> = results
+                .try_iter()
+                .unwrap()
+                .map(|result| result.unwrap().getattr("fingerprint").unwrap().extract().unwrap())
+                .collect();
+            let expected: Vec<String> = ["alice", "bob", "carol"].iter().map(|seed| HashBits::from_seed(2026, seed).hex_string()).collect();
+            assert_eq!(fingerprints, expected);
+        });
+    }
+
+    #[test]
+    fn test_invalid_arguments_raise_value_error() {
+        with_module(|py, module| {
+            let err = module.getattr("draw").unwrap().call1(("alice", 0)).unwrap_err();
+            assert!(err.is_instance_of::<PyValueError>(py));
+            let err = module.getattr("HashBits").unwrap().call1(("alice", 2026, 9)).unwrap_err();
+            assert!(err.is_instance_of::<PyValueError>(py));
+        });
+    }
+}
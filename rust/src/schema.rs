@@ -0,0 +1,249 @@
+use crate::beacon::Beacon;
+use crate::hash::HashBits;
+use crate::luck::{calculate_luck_scores, LuckScore};
+use crate::output::OmikujiResult;
+use chrono::NaiveDate;
+use clap::ValueEnum;
+use schemars::{schema_for, JsonSchema};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum SchemaVersion {
+    /// Original layout with display strings (e.g. "lucky_time": "02:40")
+    #[default]
+    #[value(name = "1")]
+    V1,
+    /// Structured values (ISO date, hour/minute, RGB, element, lat/lon)
+    #[value(name = "2")]
+    V2,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct LuckyColor {
+    /// "#RRGGBB"
+    pub hex: String,
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct LuckyDay {
+    /// ISO 8601 date, e.g. "2026-09-01"
+    #[schemars(extend("format" = "date"))]
+    pub date: String,
+    /// 1-365
+    pub day_of_year: u16,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct LuckyTime {
+    /// 0-23
+    pub hour: u8,
+    /// 0-59
+    pub minute: u8,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct LuckyElement {
+    pub symbol: String,
+    pub atomic_number: u8,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct LuckyLocation {
+    /// -90 to 90
+    pub latitude: i8,
+    /// -180 to 180
+    pub longitude: i16,
+}
+
+/// JSON output v2: same fortune as `OmikujiResult`, with typed values
+/// instead of display strings.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct OmikujiResultV2 {
+    /// Always 2
+    pub schema_version: u32,
+    pub year: u32,
+    pub seed: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beacon: Option<Beacon>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub seed_normalization: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash_scheme: Option<u8>,
+    pub lucky_number: u8,
+    pub lucky_hex: u8,
+    pub lucky_color: LuckyColor,
+    pub lucky_bits: u16,
+    pub lucky_day: LuckyDay,
+    pub lucky_time: LuckyTime,
+    pub lucky_power_of_2: u8,
+    pub lucky_ascii: char,
+    pub lucky_logic_gate: String,
+    pub lucky_emoji: String,
+    pub lucky_direction: String,
+    pub lucky_element: LuckyElement,
+    pub lucky_percent: u8,
+    pub lucky_location: LuckyLocation,
+    pub luck_scores: Vec<LuckScore>,
+    pub entropy_check: u16,
+    pub fingerprint: String,
+}
+
+impl OmikujiResultV2 {
+    /// The fortune values come from `hash` as typed values; the draw
+    /// metadata (seed fingerprint, namespace, beacon...) from `result`.
+    pub fn new(hash: &HashBits, result: &OmikujiResult) -> Result<Self, String> {
+        let base_date = NaiveDate::from_ymd_opt(result.year as i32, 1, 1)
+            .ok_or_else(|| format!("Invalid year: {}", result.year))?;
+        let day_of_year = hash.lucky_day();
+        let date = base_date + chrono::Duration::days(day_of_year as i64 - 1);
+        let [r, g, b] = hash.lucky_rgb();
+        let (symbol, atomic_number) = hash.lucky_atom();
+
+        Ok(Self {
+            schema_version: 2,
+            year: result.year,
+            seed: result.seed.clone(),
+            namespace: result.namespace.clone(),
+            beacon: result.beacon.clone(),
+            seed_normalization: result.seed_normalization.clone(),
+            hash_scheme: result.hash_scheme,
+            lucky_number: hash.lucky_number(),
+            lucky_hex: hash.lucky_hex(),
+            lucky_color: LuckyColor {
+                hex: format!("#{:02X}{:02X}{:02X}", r, g, b),
+                r,
+                g,
+                b,
+            },
+            lucky_bits: hash.lucky_bits(),
+            lucky_day: LuckyDay {
+                date: date.format("%Y-%m-%d").to_string(),
+                day_of_year,
+            },
+            lucky_time: LuckyTime {
+                hour: hash.lucky_hour(),
+                minute: hash.lucky_minute(),
+            },
+            lucky_power_of_2: hash.lucky_power_of_2(),
+            lucky_ascii: hash.lucky_ascii(),
+            lucky_logic_gate: hash.lucky_logic_gate().to_string(),
+            lucky_emoji: hash.lucky_emoji().to_string(),
+            lucky_direction: hash.lucky_direction().to_string(),
+            lucky_element: LuckyElement {
+                symbol: symbol.to_string(),
+                atomic_number,
+            },
+            lucky_percent: hash.lucky_percent(),
+            lucky_location: LuckyLocation {
+                latitude: hash.lucky_latitude(),
+                longitude: hash.lucky_longitude(),
+            },
+            luck_scores: calculate_luck_scores(&hash.luck_scores()),
+            entropy_check: hash.entropy_check(),
+            fingerprint: hash.hex_string(),
+        })
+    }
+}
+
+/// Re-derives the fortune from the result's fingerprint.
+impl TryFrom<&OmikujiResult> for OmikujiResultV2 {
+    type Error = String;
+
+    fn try_from(result: &OmikujiResult) -> Result<Self, Self::Error> {
+        Self::new(&HashBits::from_fingerprint(&result.fingerprint)?, result)
+    }
+}
+
+/// JSON Schema (draft 2020-12) of the JSON output for `version`.
+pub fn json_schema(version: SchemaVersion) -> String {
+    let schema = match version {
+        SchemaVersion::V1 => schema_for!(OmikujiResult),
+        SchemaVersion::V2 => schema_for!(OmikujiResultV2),
+    };
+    serde_json::to_string_pretty(&schema).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_result() -> OmikujiResult {
+        let hash = HashBits::from_seed(2026, "test-user");
+        OmikujiResult::from_hash(&hash, 2026, "test-user")
+    }
+
+    #[test]
+    fn test_v2_structured_fields() {
+        let v2 = OmikujiResultV2::try_from(&create_test_result()).unwrap();
+        assert_eq!(v2.schema_version, 2);
+        assert_eq!(v2.lucky_hex, 0xE3);
+        assert_eq!((v2.lucky_color.r, v2.lucky_color.g, v2.lucky_color.b), (0xE3, 0x5F, 0x21));
+        assert_eq!(v2.lucky_day.date, "2026-03-30");
+        assert_eq!(v2.lucky_time.hour, 0);
+        assert_eq!(v2.lucky_time.minute, 12);
+        assert_eq!(v2.lucky_element.symbol, "Al");
+        assert_eq!(v2.lucky_element.atomic_number, 13);
+        assert_eq!(v2.entropy_check, 0x29B);
+    }
+
+    #[test]
+    fn test_v2_lucky_bits_round_trip() {
+        let result = create_test_result();
+        let v2 = OmikujiResultV2::try_from(&result).unwrap();
+        assert_eq!(v2.lucky_bits, 0b0110_1101_0100_0111);
+    }
+
+    #[test]
+    fn test_v2_rejects_malformed_fingerprint() {
+        let mut result = create_test_result();
+        result.fingerprint = "not-a-digest".to_string();
+        assert!(OmikujiResultV2::try_from(&result).is_err());
+    }
+
+    #[test]
+    fn test_v2_matches_v1_display_strings() {
+        for i in 0..50 {
+            let seed = format!("test-{}", i);
+            let hash = HashBits::from_seed(2026, &seed);
+            let v1 = OmikujiResult::from_hash(&hash, 2026, &seed);
+            let v2 = OmikujiResultV2::new(&hash, &v1).unwrap();
+            assert_eq!(v2.lucky_color.hex, v1.lucky_color);
+            assert_eq!(format!("0x{:02X}", v2.lucky_hex), v1.lucky_hex);
+            assert_eq!(v2.lucky_day.day_of_year, v1.lucky_day_number);
+            assert!(v1.lucky_day.starts_with(&v2.lucky_day.date));
+            assert_eq!(format!("{:02}:{:02}", v2.lucky_time.hour, v2.lucky_time.minute), v1.lucky_time);
+            assert_eq!(format!("{} ({})", v2.lucky_element.symbol, v2.lucky_element.atomic_number), v1.lucky_element);
+            assert_eq!(format!("0x{:03X}", v2.entropy_check), v1.entropy_check);
+        }
+    }
+
+    #[test]
+    fn test_schema_lists_v2_fields() {
+        let schema: serde_json::Value = serde_json::from_str(&json_schema(SchemaVersion::V2)).unwrap();
+        let properties = schema["properties"].as_object().unwrap();
+        for field in ["schema_version", "lucky_day", "lucky_time", "lucky_location", "luck_scores"] {
+            assert!(properties.contains_key(field), "Missing property: {}", field);
+        }
+    }
+
+    #[test]
+    fn test_schema_v1_matches_output_fields() {
+        let schema: serde_json::Value = serde_json::from_str(&json_schema(SchemaVersion::V1)).unwrap();
+        let output: serde_json::Value = serde_json::from_str(&create_test_result().format_json()).unwrap();
+        let properties = schema["properties"].as_object().unwrap();
+        for key in output.as_object().unwrap().keys() {
+            assert!(properties.contains_key(key), "Missing property: {}", key);
+        }
+    }
+
+    #[test]
+    fn test_snapshot_json_v2_output() {
+        let v2 = OmikujiResultV2::try_from(&create_test_result()).unwrap();
+        insta::assert_json_snapshot!(v2);
+    }
+}
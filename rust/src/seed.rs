@@ -0,0 +1,178 @@
+use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD};
+use base64::Engine;
+use clap::ValueEnum;
+use gethostname::gethostname;
+use sha2::{Digest, Sha256};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+const SEED_ENV: &str = "HASH_OMIKUJI_SEED";
+const MACHINE_ID_PATHS: [&str; 2] = ["/etc/machine-id", "/var/lib/dbus/machine-id"];
+const SSH_PUBLIC_KEYS: [&str; 3] = ["id_ed25519.pub", "id_ecdsa.pub", "id_rsa.pub"];
+
+/// Where the default seed comes from when `--seed` is not given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum SeedSource {
+    /// username@hostname (changes when the machine is renamed)
+    #[default]
+    UserHost,
+    /// /etc/machine-id: one fortune per device
+    MachineId,
+    /// `git config user.email`: same fortune on every machine
+    GitEmail,
+    /// SHA256 fingerprint of ~/.ssh/id_{ed25519,ecdsa,rsa}.pub
+    SshKey,
+    /// The HASH_OMIKUJI_SEED environment variable
+    Env,
+}
+
+fn user_host() -> String {
+    let hostname = gethostname().to_string_lossy().to_string();
+    let username = env::var("USER").unwrap_or_else(|_| "anonymous".to_string());
+    format!("{}@{}", username, hostname)
+}
+
+/// The first non-empty id among `paths` (`MACHINE_ID_PATHS` outside tests).
+fn machine_id(paths: &[&str]) -> Result<String, String> {
+    paths
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .map(|id| id.trim().to_string())
+        .find(|id| !id.is_empty())
+        .ok_or_else(|| format!("No machine id found (looked in {})", paths.join(", ")))
+}
+
+/// `git config user.email`, run with `git` (a plain `git` command outside tests).
+fn git_email(mut git: Command) -> Result<String, String> {
+    let output = git
+        .args(["config", "user.email"])
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    let email = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || email.is_empty() {
+        return Err("git user.email is not set".to_string());
+    }
+    Ok(email)
+}
+
+/// OpenSSH-style fingerprint ("SHA256:<base64>") of a public key line
+/// such as "ssh-ed25519 AAAA... comment".
+pub fn ssh_fingerprint(public_key: &str) -> Result<String, String> {
+    let blob = public_key
+        .split_whitespace()
+        .nth(1)
+        .and_then(|data| STANDARD.decode(data).ok())
+        .ok_or("Malformed SSH public key")?;
+    Ok(format!("SHA256:{}", STANDARD_NO_PAD.encode(Sha256::digest(&blob))))
+}
+
+fn ssh_key() -> Result<String, String> {
+    let ssh_dir = dirs::home_dir().map(|home| home.join(".ssh")).ok_or("Cannot determine home directory")?;
+    let path: PathBuf = SSH_PUBLIC_KEYS
+        .iter()
+        .map(|name| ssh_dir.join(name))
+        .find(|path| path.exists())
+        .ok_or_else(|| format!("No SSH public key found in {}", ssh_dir.display()))?;
+    let public_key = fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    ssh_fingerprint(&public_key)
+}
+
+fn from_env() -> Result<String, String> {
+    env::var(SEED_ENV)
+        .ok()
+        .filter(|seed| !seed.is_empty())
+        .ok_or_else(|| format!("{} is not set", SEED_ENV))
+}
+
+/// Reads the seed from `source`. Everything is local; nothing is sent anywhere.
+pub fn resolve(source: SeedSource) -> Result<String, String> {
+    match source {
+        SeedSource::UserHost => Ok(user_host()),
+        SeedSource::MachineId => machine_id(&MACHINE_ID_PATHS),
+        SeedSource::GitEmail => git_email(Command::new("git")),
+        SeedSource::SshKey => ssh_key(),
+        SeedSource::Env => from_env(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_user_host_format() {
+        assert!(resolve(SeedSource::UserHost).unwrap().contains('@'));
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("hash-omikuji-seed-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_machine_id_first_non_empty() {
+        let dir = temp_dir("machine-id");
+        let empty = dir.join("empty");
+        let id = dir.join("machine-id");
+        fs::write(&empty, "\n").unwrap();
+        fs::write(&id, "0123456789abcdef0123456789abcdef\n").unwrap();
+        let missing = dir.join("missing");
+        let paths = [missing.to_str().unwrap(), empty.to_str().unwrap(), id.to_str().unwrap()];
+        assert_eq!(machine_id(&paths).unwrap(), "0123456789abcdef0123456789abcdef");
+
+        let err = machine_id(&paths[..2]).unwrap_err();
+        assert!(err.contains(paths[0]) && err.contains(paths[1]), "Unexpected error: {}", err);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// `git` reading only `config` as its global config, outside any repository.
+    fn git_with_config(dir: &Path, config: &str) -> Command {
+        let path = dir.join("gitconfig");
+        fs::write(&path, config).unwrap();
+        let mut git = Command::new("git");
+        git.current_dir(dir)
+            .env("GIT_CONFIG_GLOBAL", &path)
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            .env("GIT_CEILING_DIRECTORIES", dir)
+            .env_remove("GIT_DIR")
+            .env_remove("GIT_CONFIG");
+        git
+    }
+
+    #[test]
+    fn test_git_email_from_config() {
+        let dir = temp_dir("git-email");
+        let git = git_with_config(&dir, "[user]\n\temail = alice@example.com\n");
+        assert_eq!(git_email(git).unwrap(), "alice@example.com");
+        let git = git_with_config(&dir, "[user]\n\tname = Alice\n");
+        assert_eq!(git_email(git).unwrap_err(), "git user.email is not set");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_ssh_fingerprint_matches_ssh_keygen() {
+        // ssh-keygen -lf prints: SHA256:b5yRC/Y+pxb5lLR3Q5gyGpyluvXVxc01l62XBAxcOGw
+        let key = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIDWj79Y46dYHjPPL8ylV1yPND4QBqzSSovBjEtOOjNJ3 test";
+        assert_eq!(
+            ssh_fingerprint(key).unwrap(),
+            "SHA256:b5yRC/Y+pxb5lLR3Q5gyGpyluvXVxc01l62XBAxcOGw"
+        );
+    }
+
+    #[test]
+    fn test_ssh_fingerprint_ignores_comment() {
+        let key = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIDWj79Y46dYHjPPL8ylV1yPND4QBqzSSovBjEtOOjNJ3";
+        let with_comment = format!("{} alice@laptop\n", key);
+        assert_eq!(ssh_fingerprint(key).unwrap(), ssh_fingerprint(&with_comment).unwrap());
+    }
+
+    #[test]
+    fn test_ssh_fingerprint_rejects_garbage() {
+        assert!(ssh_fingerprint("not a key").is_err());
+        assert!(ssh_fingerprint("").is_err());
+    }
+}
@@ -0,0 +1,279 @@
+use crate::card;
+use crate::output::OmikujiResult;
+use crate::verify::verify_fortune;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::io::Read;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+const MAX_SEED_LEN: usize = 1024;
+const MAX_BODY_LEN: usize = 64 * 1024;
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+/// Forget idle clients once this many are tracked.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+pub struct Reply {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+}
+
+impl Reply {
+    fn json(status: u16, value: &impl Serialize) -> Self {
+        let mut body = serde_json::to_vec_pretty(value).unwrap();
+        body.push(b'\n');
+        Self {
+            status,
+            content_type: "application/json",
+            body,
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::json(status, &json!({ "error": message }))
+    }
+}
+
+/// Allows `limit` requests per client address per minute (fixed window).
+pub struct RateLimiter {
+    limit: u32,
+    clients: HashMap<IpAddr, (Instant, u32)>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: u32) -> Self {
+        Self {
+            limit,
+            clients: HashMap::new(),
+        }
+    }
+
+    /// Counts a request from `client`; false once it is over the limit.
+    pub fn allow(&mut self, client: IpAddr, now: Instant) -> bool {
+        if self.clients.len() >= MAX_TRACKED_CLIENTS {
+            self.clients.retain(|_, (start, _)| now.duration_since(*start) < RATE_WINDOW);
+        }
+        let (start, count) = self.clients.entry(client).or_insert((now, 0));
+        if now.duration_since(*start) >= RATE_WINDOW {
+            (*start, *count) = (now, 0);
+        }
+        *count += 1;
+        *count <= self.limit
+    }
+}
+
+#[derive(Deserialize)]
+struct VerifyRequest {
+    seed: String,
+    fortune: OmikujiResult,
+}
+
+/// Routes requests to fortunes drawn with the server's options.
+pub struct Service<'a> {
+    /// Draws the fortune for (year, raw seed)
+    pub draw: &'a dyn Fn(u32, &str) -> OmikujiResult,
+    /// Year for requests without a `year` parameter
+    pub year: u32,
+    /// Secret key, for verifying fortunes drawn with `--private`
+    pub key: Option<&'a [u8]>,
+}
+
+impl Service<'_> {
+    fn fortune(&self, query: &str) -> Result<OmikujiResult, Reply> {
+        let params: HashMap<String, String> = form_urlencoded::parse(query.as_bytes()).into_owned().collect();
+        let seed = params
+            .get("seed")
+            .filter(|seed| !seed.is_empty())
+            .ok_or_else(|| Reply::error(400, "Missing seed parameter"))?;
+        if seed.len() > MAX_SEED_LEN {
+            return Err(Reply::error(400, "Seed is too long"));
+        }
+        let year = match params.get("year") {
+            Some(year) => year
+                .parse::<u32>()
+                .ok()
+                .filter(|year| (1..=9999).contains(year))
+                .ok_or_else(|| Reply::error(400, "year must be 1-9999"))?,
+            None => self.year,
+        };
+        Ok((self.draw)(year, seed))
+    }
+
+    fn verify(&self, body: &[u8]) -> Reply {
+        let request: VerifyRequest = match serde_json::from_slice(body) {
+            Ok(request) => request,
+            Err(e) => return Reply::error(400, &format!("Expected {{\"seed\", \"fortune\"}}: {}", e)),
+        };
+        match verify_fortune(&request.fortune, &request.seed, self.key) {
+            Ok(()) => Reply::json(200, &json!({ "valid": true })),
+            Err(reason) => Reply::json(200, &json!({ "valid": false, "error": reason })),
+        }
+    }
+
+    pub fn handle(&self, method: &str, url: &str, body: &[u8]) -> Reply {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        match (method, path) {
+            ("GET", "/healthz") => Reply::json(200, &json!({ "status": "ok" })),
+            ("GET", "/fortune") => match self.fortune(query) {
+                Ok(result) => Reply::json(200, &result),
+                Err(reply) => reply,
+            },
+            ("GET", "/fortune.svg") => match self.fortune(query) {
+                Ok(result) => Reply {
+                    status: 200,
+                    content_type: "image/svg+xml",
+                    body: card::render_svg(&result).into_bytes(),
+                },
+                Err(reply) => reply,
+            },
+            ("POST", "/verify") => self.verify(body),
+            (_, "/healthz" | "/fortune" | "/fortune.svg" | "/verify") => Reply::error(405, "Method not allowed"),
+            _ => Reply::error(404, "Not found"),
+        }
+    }
+}
+
+/// Access log line. The query string is dropped so seeds never reach the log.
+pub fn log_line(method: &str, url: &str, status: u16) -> String {
+    let path = url.split_once('?').map_or(url, |(path, _)| path);
+    format!("{} {} {}", method, path, status)
+}
+
+/// Serves `service` on `addr` until the process is killed.
+pub fn run(addr: &str, service: &Service, mut limiter: RateLimiter) -> Result<(), String> {
+    let server = tiny_http::Server::http(addr).map_err(|e| format!("Failed to listen on {}: {}", addr, e))?;
+    eprintln!("Listening on http://{}", addr);
+
+    for mut request in server.incoming_requests() {
+        let method = request.method().as_str().to_string();
+        let url = request.url().to_string();
+        let client = request.remote_addr().map(|addr| addr.ip());
+
+        let reply = if client.is_some_and(|ip| !limiter.allow(ip, Instant::now())) {
+            Reply::error(429, "Too many requests")
+        } else {
+            let mut body = Vec::new();
+            let read = request.as_reader().take(MAX_BODY_LEN as u64 + 1).read_to_end(&mut body);
+            match read {
+                Err(_) => Reply::error(400, "Failed to read request body"),
+                Ok(_) if body.len() > MAX_BODY_LEN => Reply::error(413, "Request body too large"),
+                Ok(_) => service.handle(&method, &url, &body),
+            }
+        };
+
+        eprintln!("{}", log_line(&method, &url, reply.status));
+        let content_type = tiny_http::Header::from_bytes("Content-Type", reply.content_type).unwrap();
+        let response = tiny_http::Response::from_data(reply.body)
+            .with_status_code(reply.status)
+            .with_header(content_type);
+        let _ = request.respond(response);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::HashBits;
+
+    fn draw(year: u32, seed: &str) -> OmikujiResult {
+        OmikujiResult::from_hash(&HashBits::from_seed(year, seed), year, seed)
+    }
+
+    fn service() -> Service<'static> {
+        Service { draw: &draw, year: 2026, key: None }
+    }
+
+    fn body_json(reply: &Reply) -> serde_json::Value {
+        serde_json::from_slice(&reply.body).unwrap()
+    }
+
+    #[test]
+    fn test_healthz() {
+        let reply = service().handle("GET", "/healthz", b"");
+        assert_eq!(reply.status, 200);
+        assert_eq!(body_json(&reply)["status"], "ok");
+    }
+
+    #[test]
+    fn test_fortune_matches_cli_result() {
+        let reply = service().handle("GET", "/fortune?seed=test-user&year=2026", b"");
+        assert_eq!(reply.status, 200);
+        assert_eq!(reply.content_type, "application/json");
+        let result: OmikujiResult = serde_json::from_slice(&reply.body).unwrap();
+        assert_eq!(result, draw(2026, "test-user"));
+    }
+
+    #[test]
+    fn test_fortune_decodes_seed() {
+        let reply = service().handle("GET", "/fortune?seed=alice%40example.com&year=2026", b"");
+        let result: OmikujiResult = serde_json::from_slice(&reply.body).unwrap();
+        assert_eq!(result.fingerprint, draw(2026, "alice@example.com").fingerprint);
+    }
+
+    #[test]
+    fn test_fortune_defaults_to_service_year() {
+        let service = Service { draw: &draw, year: 2030, key: None };
+        let reply = service.handle("GET", "/fortune?seed=test-user", b"");
+        let result: OmikujiResult = serde_json::from_slice(&reply.body).unwrap();
+        assert_eq!(result, draw(2030, "test-user"));
+    }
+
+    #[test]
+    fn test_fortune_rejects_bad_params() {
+        assert_eq!(service().handle("GET", "/fortune", b"").status, 400);
+        assert_eq!(service().handle("GET", "/fortune?seed=a&year=abc", b"").status, 400);
+        assert_eq!(service().handle("GET", "/fortune?seed=a&year=0", b"").status, 400);
+    }
+
+    #[test]
+    fn test_fortune_svg() {
+        let reply = service().handle("GET", "/fortune.svg?seed=test-user&year=2026", b"");
+        assert_eq!(reply.status, 200);
+        assert_eq!(reply.content_type, "image/svg+xml");
+        assert!(String::from_utf8(reply.body).unwrap().starts_with("<svg"));
+    }
+
+    #[test]
+    fn test_verify() {
+        let fortune = draw(2026, "alice");
+        let body = json!({ "seed": "alice", "fortune": fortune }).to_string();
+        let reply = service().handle("POST", "/verify", body.as_bytes());
+        assert_eq!(body_json(&reply)["valid"], true);
+
+        let body = json!({ "seed": "bob", "fortune": fortune }).to_string();
+        let reply = service().handle("POST", "/verify", body.as_bytes());
+        assert_eq!(body_json(&reply)["valid"], false);
+
+        assert_eq!(service().handle("POST", "/verify", b"{}").status, 400);
+    }
+
+    #[test]
+    fn test_unknown_routes_and_methods() {
+        assert_eq!(service().handle("GET", "/nope", b"").status, 404);
+        assert_eq!(service().handle("GET", "/verify", b"").status, 405);
+        assert_eq!(service().handle("DELETE", "/fortune", b"").status, 405);
+    }
+
+    #[test]
+    fn test_log_line_drops_query() {
+        let line = log_line("GET", "/fortune?seed=alice@example.com&year=2026", 200);
+        assert_eq!(line, "GET /fortune 200");
+        assert!(!line.contains("alice"));
+    }
+
+    #[test]
+    fn test_rate_limiter_window() {
+        let mut limiter = RateLimiter::new(2);
+        let client: IpAddr = "127.0.0.1".parse().unwrap();
+        let other: IpAddr = "10.0.0.1".parse().unwrap();
+        let now = Instant::now();
+        assert!(limiter.allow(client, now));
+        assert!(limiter.allow(client, now));
+        assert!(!limiter.allow(client, now));
+        assert!(limiter.allow(other, now));
+        assert!(limiter.allow(client, now + RATE_WINDOW));
+    }
+}
@@ -0,0 +1,130 @@
+use crate::luck::{LuckType, Rank};
+use crate::output::OmikujiResult;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct LuckStats {
+    pub luck_type: LuckType,
+    pub mean: f64,
+    pub min: u8,
+    pub max: u8,
+}
+
+/// Luck score distribution over a sample of fortunes, and where one
+/// fortune's total luck falls in it.
+#[derive(Debug, Serialize)]
+pub struct Stats {
+    pub year: u32,
+    pub samples: usize,
+    pub luck: Vec<LuckStats>,
+    /// Share of scores in each rank, in `Rank::ALL` order
+    pub ranks: Vec<(Rank, f64)>,
+    pub total: u32,
+    /// Your rank among the samples as a percentage: (samples with a higher
+    /// total luck + 1) / samples, so the best sample is in the top 100/n%
+    pub top_percent: f64,
+}
+
+fn total_score(result: &OmikujiResult) -> u32 {
+    result.luck_scores.iter().map(|s| s.score as u32).sum()
+}
+
+/// Statistics of `samples` (which must not be empty), compared with `yours`.
+pub fn compute(samples: &[OmikujiResult], yours: &OmikujiResult) -> Stats {
+    let n = samples.len();
+    let luck = LuckType::ALL
+        .iter()
+        .map(|&luck_type| {
+            let scores: Vec<u8> = samples
+                .iter()
+                .flat_map(|r| r.luck_scores.iter().filter(|s| s.luck_type == luck_type).map(|s| s.score))
+                .collect();
+            LuckStats {
+                luck_type,
+                mean: scores.iter().map(|&s| s as f64).sum::<f64>() / scores.len() as f64,
+                min: scores.iter().copied().min().unwrap_or(0),
+                max: scores.iter().copied().max().unwrap_or(0),
+            }
+        })
+        .collect();
+    let all_scores = samples.iter().flat_map(|r| r.luck_scores.iter());
+    let score_count = all_scores.clone().count() as f64;
+    let ranks = Rank::ALL
+        .iter()
+        .map(|&rank| (rank, all_scores.clone().filter(|s| s.rank == rank).count() as f64 * 100.0 / score_count))
+        .collect();
+    let total = total_score(yours);
+    let better = samples.iter().filter(|r| total_score(r) > total).count();
+    Stats {
+        year: yours.year,
+        samples: n,
+        luck,
+        ranks,
+        total,
+        top_percent: (better + 1) as f64 * 100.0 / n as f64,
+    }
+}
+
+impl Stats {
+    pub fn format_text(&self) -> String {
+        let mut out = format!("Luck score statistics for {} over {} seeds\n\n", self.year, self.samples);
+        out.push_str(&format!("{:<18} {:>6} {:>5} {:>5}\n", "Luck Type", "Mean", "Min", "Max"));
+        for stats in &self.luck {
+            out.push_str(&format!(
+                "{:<18} {:>6.1} {:>5} {:>5}\n",
+                stats.luck_type.name(),
+                stats.mean,
+                stats.min,
+                stats.max
+            ));
+        }
+        let ranks: Vec<String> = self
+            .ranks
+            .iter()
+            .map(|(rank, percent)| format!("{} {:.1}%", rank.as_str(), percent))
+            .collect();
+        out.push_str(&format!("\nRanks : {}\n", ranks.join(" | ")));
+        out.push_str(&format!("Your total luck {} is in the top {:.1}%\n", self.total, self.top_percent));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::HashBits;
+
+    fn draw(seed: &str) -> OmikujiResult {
+        OmikujiResult::from_hash(&HashBits::from_seed(2026, seed), 2026, seed)
+    }
+
+    #[test]
+    fn test_compute() {
+        let samples: Vec<OmikujiResult> = (0..200).map(|i| draw(&format!("sample-{}", i))).collect();
+        let stats = compute(&samples, &samples[0]);
+        assert_eq!(stats.samples, 200);
+        assert_eq!(stats.luck.len(), 16);
+        assert!(stats.luck.iter().all(|l| l.min as f64 <= l.mean && l.mean <= l.max as f64));
+        let total: f64 = stats.ranks.iter().map(|(_, p)| p).sum();
+        assert!((total - 100.0).abs() < 1e-9);
+        assert!(stats.top_percent > 0.0 && stats.top_percent <= 100.0);
+    }
+
+    #[test]
+    fn test_best_sample_is_top() {
+        let samples: Vec<OmikujiResult> = (0..50).map(|i| draw(&format!("sample-{}", i))).collect();
+        let best = samples.iter().max_by_key(|r| total_score(r)).unwrap();
+        assert_eq!(compute(&samples, best).top_percent, 2.0);
+        let worst = samples.iter().min_by_key(|r| total_score(r)).unwrap();
+        let tied = samples.iter().filter(|r| total_score(r) == total_score(worst)).count();
+        assert_eq!(compute(&samples, worst).top_percent, (51 - tied) as f64 * 2.0);
+    }
+
+    #[test]
+    fn test_format_text() {
+        let samples = vec![draw("a"), draw("b")];
+        let text = compute(&samples, &samples[0]).format_text();
+        assert!(text.starts_with("Luck score statistics for 2026 over 2 seeds"));
+        assert!(text.contains("Your total luck"));
+    }
+}
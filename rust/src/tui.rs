@@ -0,0 +1,457 @@
+use crate::hash::{DeriveOptions, HashBits};
+use crate::luck::{sorted_by_score, LuckScore, Rank};
+use crate::normalize::{normalize, Normalization};
+use crate::output::OmikujiResult;
+use crate::privacy::SeedDisplay;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Bar, BarChart, BarGroup, Block, Paragraph, Row, Table, TableState, Tabs};
+use ratatui::{DefaultTerminal, Frame};
+use std::io;
+use std::time::{Duration, Instant};
+
+/// Teammates' fortunes for the leaderboard, as each of them would draw it.
+/// The keys in `options` and `display` are ignored: our `--private` key
+/// would give fortunes only we can compute, not the ones they draw.
+pub fn team_fortunes(
+    members: &[String],
+    year: u32,
+    options: &DeriveOptions,
+    steps: &[Normalization],
+    display: &SeedDisplay,
+) -> Vec<(String, OmikujiResult)> {
+    let options = DeriveOptions { key: None, ..*options };
+    let display = SeedDisplay {
+        key: None,
+        ..display.clone()
+    };
+    members
+        .iter()
+        .map(|name| {
+            let (seed, steps) = normalize(name, steps);
+            let hash = HashBits::derive(year, &seed, &options);
+            let result = OmikujiResult::from_hash(&hash, year, &seed)
+                .with_seed_display(&seed, &display)
+                .with_seed_normalization(&steps);
+            (name.clone(), result)
+        })
+        .collect()
+}
+
+/// How many earlier years the History tab shows.
+pub const HISTORY_YEARS: u32 = 5;
+const TICK: Duration = Duration::from_millis(80);
+const SHAKE_FRAMES: u16 = 16;
+/// Horizontal offsets of the box while it is shaken.
+const SHAKE_OFFSETS: [u16; 8] = [0, 2, 4, 2, 0, 1, 3, 1];
+const BOX: [&str; 6] = [
+    "╭──────╮",
+    "│  御  │",
+    "│  神  │",
+    "│  籤  │",
+    "│      │",
+    "╰──┬┬──╯",
+];
+const STICK: [&str; 3] = ["   ││", "   ││", "   ╰╯"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tab {
+    Fortune,
+    Charts,
+    History,
+    Team,
+}
+
+impl Tab {
+    pub const ALL: [Tab; 4] = [Tab::Fortune, Tab::Charts, Tab::History, Tab::Team];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Tab::Fortune => "Fortune",
+            Tab::Charts => "Charts",
+            Tab::History => "History",
+            Tab::Team => "Team",
+        }
+    }
+
+    fn index(&self) -> usize {
+        Tab::ALL.iter().position(|tab| tab == self).unwrap()
+    }
+}
+
+fn total_score(result: &OmikujiResult) -> u32 {
+    result.luck_scores.iter().map(|s| s.score as u32).sum()
+}
+
+fn best_luck(result: &OmikujiResult) -> &LuckScore {
+    sorted_by_score(&result.luck_scores)[0]
+}
+
+fn rank_color(rank: Rank) -> Color {
+    match rank {
+        Rank::Excellent => Color::LightYellow,
+        Rank::Good => Color::LightGreen,
+        Rank::Normal => Color::White,
+        Rank::Bad => Color::LightBlue,
+        Rank::Terrible => Color::DarkGray,
+    }
+}
+
+/// "#RRGGBB" as a terminal color.
+fn lucky_color(result: &OmikujiResult) -> Color {
+    let channel = |i: usize| u8::from_str_radix(result.lucky_color.get(i..i + 2).unwrap_or("80"), 16).unwrap_or(0x80);
+    Color::Rgb(channel(1), channel(3), channel(5))
+}
+
+pub struct App {
+    result: OmikujiResult,
+    /// The same seed in earlier years, newest first
+    history: Vec<OmikujiResult>,
+    /// Team members' fortunes, best total score first
+    team: Vec<(String, OmikujiResult)>,
+    tab: Tab,
+    selected: usize,
+    shake_frames_left: u16,
+    reveal_fingerprint: bool,
+    quit: bool,
+}
+
+impl App {
+    pub fn new(result: OmikujiResult, history: Vec<OmikujiResult>, mut team: Vec<(String, OmikujiResult)>) -> Self {
+        team.sort_by_key(|(_, result)| std::cmp::Reverse(total_score(result)));
+        Self {
+            result,
+            history,
+            team,
+            tab: Tab::Fortune,
+            selected: 0,
+            shake_frames_left: SHAKE_FRAMES,
+            reveal_fingerprint: false,
+            quit: false,
+        }
+    }
+
+    pub fn is_shaking(&self) -> bool {
+        self.shake_frames_left > 0
+    }
+
+    pub fn tick(&mut self) {
+        self.shake_frames_left = self.shake_frames_left.saturating_sub(1);
+    }
+
+    fn select_tab(&mut self, tab: Tab) {
+        self.tab = tab;
+        self.selected = 0;
+    }
+
+    fn rows(&self) -> usize {
+        match self.tab {
+            Tab::History => self.history.len(),
+            Tab::Team => self.team.len(),
+            Tab::Fortune | Tab::Charts => 0,
+        }
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode) {
+        let tab = self.tab.index();
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Tab | KeyCode::Right | KeyCode::Char('l') => self.select_tab(Tab::ALL[(tab + 1) % Tab::ALL.len()]),
+            KeyCode::BackTab | KeyCode::Left | KeyCode::Char('h') => {
+                self.select_tab(Tab::ALL[(tab + Tab::ALL.len() - 1) % Tab::ALL.len()])
+            }
+            KeyCode::Char(c @ '1'..='4') => self.select_tab(Tab::ALL[c as usize - '1' as usize]),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.rows().saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Char('f') => self.reveal_fingerprint = !self.reveal_fingerprint,
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                self.select_tab(Tab::Fortune);
+                self.shake_frames_left = SHAKE_FRAMES;
+            }
+            _ => {}
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let [tabs_area, body, footer] =
+            Layout::vertical([Constraint::Length(3), Constraint::Min(0), Constraint::Length(2)]).areas(frame.area());
+
+        let title = format!(" 🎍 Hash-Omikuji {}{} ", self.result.year, self.result.namespace_suffix());
+        let tabs = Tabs::new(Tab::ALL.iter().enumerate().map(|(i, tab)| format!("{} {}", i + 1, tab.title())))
+            .select(self.tab.index())
+            .block(Block::bordered().title(title))
+            .highlight_style(Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+        frame.render_widget(tabs, tabs_area);
+
+        match self.tab {
+            Tab::Fortune => self.render_fortune(frame, body),
+            Tab::Charts => self.render_charts(frame, body),
+            Tab::History => self.render_history(frame, body),
+            Tab::Team => self.render_team(frame, body),
+        }
+
+        let fingerprint = if self.reveal_fingerprint {
+            Span::raw(self.result.fingerprint.clone())
+        } else {
+            Span::raw("hidden").dim()
+        };
+        let footer_lines = vec![
+            Line::from(vec![Span::raw(format!(" {} | fingerprint: ", self.result.seed)), fingerprint]),
+            Line::from(" ←/→ tabs  ↑/↓ select  space shake again  f fingerprint  q quit").dim(),
+        ];
+        frame.render_widget(Paragraph::new(footer_lines), footer);
+    }
+
+    fn render_fortune(&self, frame: &mut Frame, area: Rect) {
+        let [box_area, detail_area] =
+            Layout::horizontal([Constraint::Length(16), Constraint::Min(0)]).areas(area);
+
+        let offset = if self.is_shaking() {
+            SHAKE_OFFSETS[self.shake_frames_left as usize % SHAKE_OFFSETS.len()] as usize
+        } else {
+            2
+        };
+        let stick: &[&str] = if self.is_shaking() { &[] } else { &STICK };
+        let mut art = vec![Line::default()];
+        art.extend(BOX.iter().chain(stick).map(|line| Line::from(format!("{}{}", " ".repeat(offset), line))));
+        frame.render_widget(Paragraph::new(art), box_area);
+
+        if self.is_shaking() {
+            frame.render_widget(Paragraph::new("\nShaking the box...").italic(), detail_area);
+            return;
+        }
+
+        let r = &self.result;
+        let field = |name: &str, value: String| Line::from(vec![Span::raw(format!("{:<18}: ", name)).dim(), Span::raw(value)]);
+        let mut lines = vec![
+            Line::default(),
+            field("Lucky Number", r.lucky_number.to_string()),
+            Line::from(vec![
+                Span::raw(format!("{:<18}: ", "Lucky Color")).dim(),
+                Span::raw("██ ").fg(lucky_color(r)),
+                Span::raw(r.lucky_color.clone()),
+            ]),
+            field("Lucky Day", r.lucky_day.clone()),
+            field("Lucky Time", r.lucky_time.clone()),
+            field("Lucky Emoji", r.lucky_emoji.clone()),
+            field("Lucky Direction", r.lucky_direction.clone()),
+            field("Lucky Element", r.lucky_element.clone()),
+            field("Lucky Location", format!("{}°, {}°", r.lucky_latitude, r.lucky_longitude)),
+            Line::default(),
+            Line::from("Top luck").bold(),
+        ];
+        for score in sorted_by_score(&r.luck_scores).into_iter().take(3) {
+            lines.push(Line::from(vec![
+                Span::raw(format!("  {:<17}", score.luck_type.name())),
+                Span::raw(format!("{:>3}  ", score.score)),
+                Span::raw(score.rank.as_str()).fg(rank_color(score.rank)),
+            ]));
+        }
+        frame.render_widget(Paragraph::new(lines), detail_area);
+    }
+
+    fn render_charts(&self, frame: &mut Frame, area: Rect) {
+        let bars: Vec<Bar> = self
+            .result
+            .luck_scores
+            .iter()
+            .map(|s| {
+                Bar::default()
+                    .value(s.score as u64)
+                    .label(Line::from(format!("{:<17}", s.luck_type.name())))
+                    .text_value(format!("{} {}", s.score, s.rank.as_str()))
+                    .style(Style::new().fg(rank_color(s.rank)))
+                    .value_style(Style::new().fg(Color::Black).bg(rank_color(s.rank)))
+            })
+            .collect();
+        let chart = BarChart::default()
+            .block(Block::bordered().title(" Luck Scores "))
+            .direction(Direction::Horizontal)
+            .bar_width(1)
+            .bar_gap(0)
+            .max(100)
+            .data(BarGroup::default().bars(&bars));
+        frame.render_widget(chart, area);
+    }
+
+    fn render_history(&self, frame: &mut Frame, area: Rect) {
+        let rows = self.history.iter().map(|r| {
+            let best = best_luck(r);
+            Row::new(vec![
+                r.year.to_string(),
+                r.lucky_number.to_string(),
+                r.lucky_color.clone(),
+                format!("{} {}", best.luck_type.name(), best.score),
+                total_score(r).to_string(),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [Constraint::Length(6), Constraint::Length(8), Constraint::Length(9), Constraint::Length(20), Constraint::Length(6)],
+        )
+        .header(Row::new(["Year", "Number", "Color", "Best luck", "Total"]).bold())
+        .block(Block::bordered().title(" Previous years "))
+        .row_highlight_style(Style::new().reversed());
+        let mut state = TableState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+
+    fn render_team(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered().title(" Team leaderboard ");
+        if self.team.is_empty() {
+            let hint = "\nNo team configured. Use `tui --team alice,bob` or `team = [...]` in config.toml.";
+            frame.render_widget(Paragraph::new(hint).block(block), area);
+            return;
+        }
+        let rows = self.team.iter().enumerate().map(|(i, (name, r))| {
+            let best = best_luck(r);
+            Row::new(vec![
+                format!("{}", i + 1),
+                name.clone(),
+                total_score(r).to_string(),
+                format!("{} {}", best.luck_type.name(), best.score),
+                r.lucky_emoji.clone(),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [Constraint::Length(3), Constraint::Min(12), Constraint::Length(6), Constraint::Length(20), Constraint::Length(4)],
+        )
+        .header(Row::new(["#", "Name", "Total", "Best luck", ""]).bold())
+        .block(block)
+        .row_highlight_style(Style::new().reversed());
+        let mut state = TableState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, mut app: App) -> io::Result<()> {
+    let mut last_tick = Instant::now();
+    while !app.quit {
+        terminal.draw(|frame| app.render(frame))?;
+        let timeout = TICK.saturating_sub(last_tick.elapsed());
+        if event::poll(timeout)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    app.handle_key(key.code);
+                }
+            }
+        }
+        if last_tick.elapsed() >= TICK {
+            app.tick();
+            last_tick = Instant::now();
+        }
+    }
+    Ok(())
+}
+
+/// Runs the full-screen UI until the user quits.
+pub fn run(app: App) -> Result<(), String> {
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, app);
+    ratatui::restore();
+    result.map_err(|e| format!("Terminal error: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn fortune(year: u32, seed: &str) -> OmikujiResult {
+        OmikujiResult::from_hash(&HashBits::from_seed(year, seed), year, seed)
+    }
+
+    fn test_app() -> App {
+        let history = vec![fortune(2025, "test-user"), fortune(2024, "test-user")];
+        let team = ["alice", "bob", "carol"].iter().map(|name| (name.to_string(), fortune(2026, name))).collect();
+        App::new(fortune(2026, "test-user"), history, team)
+    }
+
+    fn screen(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content()
+            .chunks(buffer.area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_shake_then_reveal() {
+        let mut app = test_app();
+        assert!(screen(&app).contains("Shaking the box"));
+        for _ in 0..SHAKE_FRAMES {
+            app.tick();
+        }
+        let text = screen(&app);
+        assert!(!text.contains("Shaking the box"));
+        assert!(text.contains("Lucky Number"));
+        assert!(text.contains("95"));
+    }
+
+    #[test]
+    fn test_tab_navigation_wraps() {
+        let mut app = test_app();
+        app.handle_key(KeyCode::Left);
+        assert_eq!(app.tab, Tab::Team);
+        app.handle_key(KeyCode::Tab);
+        assert_eq!(app.tab, Tab::Fortune);
+        app.handle_key(KeyCode::Char('3'));
+        assert_eq!(app.tab, Tab::History);
+        assert!(screen(&app).contains("2025"));
+    }
+
+    #[test]
+    fn test_fingerprint_toggle() {
+        let mut app = test_app();
+        let fingerprint = app.result.fingerprint.clone();
+        assert!(!screen(&app).contains(&fingerprint));
+        app.handle_key(KeyCode::Char('f'));
+        assert!(screen(&app).contains(&fingerprint));
+    }
+
+    #[test]
+    fn test_team_sorted_by_total_and_selection_clamped() {
+        let mut app = test_app();
+        let totals: Vec<u32> = app.team.iter().map(|(_, r)| total_score(r)).collect();
+        assert!(totals.windows(2).all(|w| w[0] >= w[1]));
+
+        app.handle_key(KeyCode::Char('4'));
+        for _ in 0..10 {
+            app.handle_key(KeyCode::Down);
+        }
+        assert_eq!(app.selected, 2);
+        assert!(screen(&app).contains("alice"));
+    }
+
+    #[test]
+    fn test_quit_keys() {
+        let mut app = test_app();
+        app.handle_key(KeyCode::Char('q'));
+        assert!(app.quit);
+    }
+
+    #[test]
+    fn test_team_fortunes_ignore_private_key() {
+        let key = [7u8; 32];
+        let options = DeriveOptions {
+            namespace: Some("acme"),
+            key: Some(&key),
+            ..Default::default()
+        };
+        let team = team_fortunes(&["Bob ".to_string()], 2026, &options, &[Normalization::Trim], &SeedDisplay::default());
+        let public = DeriveOptions { key: None, ..options };
+        let expected = OmikujiResult::from_hash(&HashBits::derive(2026, "Bob", &public), 2026, "Bob")
+            .with_seed_normalization(&[Normalization::Trim]);
+        assert_eq!(team, vec![("Bob ".to_string(), expected)]);
+    }
+}
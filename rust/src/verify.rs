@@ -0,0 +1,105 @@
+use crate::hash::{DeriveOptions, HashBits, HashScheme};
+use crate::output::OmikujiResult;
+use crate::normalize::{normalize, Normalization};
+use clap::ValueEnum;
+
+/// Recomputes `fortune` from `seed` with the scheme, namespace, beacon and
+/// seed normalization recorded in it, and checks every derived field.
+/// `key` is needed for fortunes drawn with `--private`.
+pub fn verify_fortune(fortune: &OmikujiResult, seed: &str, key: Option<&[u8]>) -> Result<(), String> {
+    fortune.validate()?;
+    let steps = fortune
+        .seed_normalization
+        .iter()
+        .map(|name| Normalization::from_str(name, false).map_err(|_| format!("Unknown seed normalization: {}", name)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let (seed, steps) = normalize(seed, &steps);
+    let scheme = match fortune.hash_scheme {
+        Some(number) => HashScheme::from_number(number)?,
+        None => HashScheme::V1,
+    };
+    let options = DeriveOptions {
+        scheme,
+        namespace: fortune.namespace.as_deref(),
+        beacon: fortune.beacon.as_ref(),
+        key,
+    };
+    let hash = HashBits::derive(fortune.year, &seed, &options);
+    if hash.hex_string() != fortune.fingerprint {
+        return Err("Fingerprint does not match this seed".to_string());
+    }
+
+    let mut expected = OmikujiResult::from_hash(&hash, fortune.year, &seed).with_seed_normalization(&steps);
+    // The displayed seed depends on display options, not on the draw
+    expected.seed = fortune.seed.clone();
+    if &expected != fortune {
+        return Err("Fortune fields do not match its fingerprint".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::beacon::Beacon;
+
+    fn draw(seed: &str, options: &DeriveOptions) -> OmikujiResult {
+        OmikujiResult::from_hash(&HashBits::derive(2026, seed, options), 2026, seed)
+    }
+
+    #[test]
+    fn test_verify_plain_fortune() {
+        let fortune = draw("alice", &DeriveOptions::default());
+        assert!(verify_fortune(&fortune, "alice", None).is_ok());
+        assert!(verify_fortune(&fortune, "bob", None).is_err());
+    }
+
+    #[test]
+    fn test_verify_uses_recorded_options() {
+        let beacon = Beacon {
+            round: 7,
+            randomness: "cd".repeat(32),
+        };
+        let options = DeriveOptions {
+            scheme: HashScheme::V2,
+            namespace: Some("acme"),
+            beacon: Some(&beacon),
+            key: None,
+        };
+        let fortune = draw("alice", &options);
+        assert!(verify_fortune(&fortune, "alice", None).is_ok());
+
+        let mut moved = fortune.clone();
+        moved.namespace = Some("globex".to_string());
+        assert!(verify_fortune(&moved, "alice", None).is_err());
+    }
+
+    #[test]
+    fn test_verify_applies_recorded_normalization() {
+        let fortune = draw("alice", &DeriveOptions::default()).with_seed_normalization(&[Normalization::Casefold]);
+        assert!(verify_fortune(&fortune, "ALICE", None).is_ok());
+
+        let fortune = draw("strasse", &DeriveOptions::default()).with_seed_normalization(&[Normalization::Casefold]);
+        assert!(verify_fortune(&fortune, "Straße", None).is_ok());
+    }
+
+    #[test]
+    fn test_verify_rejects_edited_field() {
+        let mut fortune = draw("alice", &DeriveOptions::default());
+        fortune.lucky_number = fortune.lucky_number.wrapping_add(1);
+        let err = verify_fortune(&fortune, "alice", None).unwrap_err();
+        assert!(err.starts_with("lucky_number "), "Unexpected error: {}", err);
+        assert!(err.contains("does not match the fingerprint"), "Unexpected error: {}", err);
+    }
+
+    #[test]
+    fn test_verify_private_fortune_needs_key() {
+        let options = DeriveOptions {
+            key: Some(b"secret"),
+            ..Default::default()
+        };
+        let fortune = draw("alice", &options);
+        assert!(verify_fortune(&fortune, "alice", None).is_err());
+        assert!(verify_fortune(&fortune, "alice", Some(b"secret")).is_ok());
+    }
+}
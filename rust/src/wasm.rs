@@ -0,0 +1,37 @@
+use crate::chart::ScoreStyle;
+use crate::hash::HashBits;
+use crate::output::OmikujiResult;
+use wasm_bindgen::prelude::*;
+
+fn fortune(seed: &str, year: u32) -> OmikujiResult {
+    OmikujiResult::from_hash(&HashBits::from_seed(year, seed), year, seed)
+}
+
+/// The fortune as JSON, same as `hash-omikuji --seed <seed> --force-year <year> --json`.
+#[wasm_bindgen]
+pub fn draw(seed: &str, year: u32) -> String {
+    fortune(seed, year).format_json()
+}
+
+/// The fortune slip as text, same as the CLI's default output.
+#[wasm_bindgen(js_name = drawText)]
+pub fn draw_text(seed: &str, year: u32, short: bool) -> String {
+    fortune(seed, year).format_text(short, false, ScoreStyle::Plain, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draw_matches_cli_json() {
+        let json = draw("test-user", 2026);
+        let result = OmikujiResult::from_json(&json).unwrap();
+        assert_eq!(result.fingerprint, HashBits::from_seed(2026, "test-user").hex_string());
+    }
+
+    #[test]
+    fn test_draw_text_has_header() {
+        assert!(draw_text("test-user", 2026, true).starts_with("🎍 Hash-Omikuji 2026 🎍"));
+    }
+}